@@ -1,7 +1,7 @@
 use sp_core::{Pair, Public, sr25519};
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
-	SudoConfig, SystemConfig, WASM_BINARY, Signature
+	AccountId, AuraConfig, Balance, BalancesConfig, GenesisConfig, GrandpaConfig,
+	KittiesConfig, SudoConfig, SystemConfig, WASM_BINARY, Signature
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
@@ -30,6 +30,16 @@ pub fn get_account_id_from_seed<TPublic: Public>(seed: &str) -> AccountId where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
+/// Generate a female and a male kitty for an account, so it can breed right away.
+pub fn breedable_kitties(owner: AccountId) -> Vec<(AccountId, [u8; 16], Option<Balance>)> {
+	vec![
+		// odd first byte is female
+		(owner.clone(), [1, 42, 17, 205, 93, 160, 7, 254, 68, 131, 19, 77, 240, 9, 116, 58], None),
+		// even first byte is male
+		(owner, [2, 199, 86, 31, 140, 72, 225, 3, 187, 50, 164, 96, 11, 238, 129, 45], None),
+	]
+}
+
 /// Generate an Aura authority key.
 pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 	(
@@ -62,6 +72,11 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 			],
+			// Initial kitties
+			[
+				breedable_kitties(get_account_id_from_seed::<sr25519::Public>("Alice")),
+				breedable_kitties(get_account_id_from_seed::<sr25519::Public>("Bob")),
+			].concat(),
 			true,
 		),
 		// Bootnodes
@@ -110,6 +125,11 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 				get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 			],
+			// Initial kitties
+			[
+				breedable_kitties(get_account_id_from_seed::<sr25519::Public>("Alice")),
+				breedable_kitties(get_account_id_from_seed::<sr25519::Public>("Bob")),
+			].concat(),
 			true,
 		),
 		// Bootnodes
@@ -131,6 +151,7 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	initial_kitties: Vec<(AccountId, [u8; 16], Option<Balance>)>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -153,7 +174,9 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			key: root_key,
 		},
-		kitties: Default::default(),
+		kitties: KittiesConfig {
+			kitties: initial_kitties,
+		},
 		nft: Default::default(),
 	}
 }
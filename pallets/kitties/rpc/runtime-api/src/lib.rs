@@ -0,0 +1,38 @@
+//! Runtime API definition for the kitties pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use pallet_kitties::{KittyInfo, Pedigree};
+
+sp_api::decl_runtime_apis! {
+	pub trait KittiesApi<AccountId, KittyIndex, Balance, BlockNumber> where
+		AccountId: Codec,
+		KittyIndex: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Ids of the kitties owned by `owner`, in ascending order, starting from `start`.
+		/// At most `count` ids are returned.
+		fn kitties_of(owner: AccountId, start: KittyIndex, count: u32) -> Vec<KittyIndex>;
+		/// Details of a single kitty. None if the kitty does not exist.
+		fn kitty(kitty_id: KittyIndex) -> Option<KittyInfo<AccountId, Balance>>;
+		/// Kitties that are for sale and their prices, in ascending order of id, starting from `start`.
+		/// At most `count` entries are returned.
+		fn listed_kitties(start: KittyIndex, count: u32) -> Vec<(KittyIndex, Balance)>;
+		/// The price a kitty can be bought for at the current block, including
+		/// the decayed price of a dutch listing. None if the kitty is not for sale.
+		fn current_price(kitty_id: KittyIndex) -> Option<Balance>;
+		/// Parents, generation and birth block of a kitty.
+		fn pedigree(kitty_id: KittyIndex) -> Option<Pedigree<KittyIndex, BlockNumber>>;
+		/// Ancestors of a kitty and the depth they are found at, up to `depth` generations back.
+		/// The depth is capped by `pallet_kitties::MAX_ANCESTRY_DEPTH`.
+		fn ancestors(kitty_id: KittyIndex, depth: u32) -> Vec<(KittyIndex, u32)>;
+		/// The price of creating a new kitty.
+		fn mint_price() -> Balance;
+		/// The number of kitties that can still be created.
+		fn remaining_supply() -> u32;
+	}
+}
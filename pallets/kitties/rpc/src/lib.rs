@@ -0,0 +1,210 @@
+//! RPC interface for the kitties pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_kitties_rpc_runtime_api::{KittiesApi as KittiesRuntimeApi, KittyInfo, Pedigree};
+
+/// Maximum number of entries returned by a single paginated call.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Error code for failures when calling into the runtime.
+const RUNTIME_ERROR: i64 = 1;
+
+#[rpc]
+pub trait KittiesApi<BlockHash, AccountId, KittyIndex, Balance, BlockNumber> {
+	/// Get the ids of the kitties owned by an account.
+	///
+	/// Ids are returned in ascending order, starting from `start` (inclusive).
+	/// At most `count` ids are returned, capped by `MAX_PAGE_SIZE`.
+	#[rpc(name = "kitties_kittiesOf")]
+	fn kitties_of(
+		&self,
+		owner: AccountId,
+		start: Option<KittyIndex>,
+		count: Option<u32>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<KittyIndex>>;
+
+	/// Get the owner, DNA, gender and price of a kitty.
+	#[rpc(name = "kitties_kitty")]
+	fn kitty(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<KittyInfo<AccountId, Balance>>>;
+
+	/// Get the kitties that are for sale and their prices.
+	///
+	/// Entries are returned in ascending order of id, starting from `start` (inclusive).
+	/// At most `count` entries are returned, capped by `MAX_PAGE_SIZE`.
+	#[rpc(name = "kitties_listedKitties")]
+	fn listed_kitties(
+		&self,
+		start: Option<KittyIndex>,
+		count: Option<u32>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(KittyIndex, Balance)>>;
+
+	/// Get the price a kitty can be bought for, including the decayed price of a dutch listing.
+	#[rpc(name = "kitties_currentPrice")]
+	fn current_price(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<Balance>>;
+
+	/// Get the parents, generation and birth block of a kitty.
+	#[rpc(name = "kitties_pedigree")]
+	fn pedigree(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<Pedigree<KittyIndex, BlockNumber>>>;
+
+	/// Get the ancestors of a kitty and the depth they are found at.
+	#[rpc(name = "kitties_ancestors")]
+	fn ancestors(
+		&self,
+		kitty_id: KittyIndex,
+		depth: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(KittyIndex, u32)>>;
+
+	/// Get the price of creating a new kitty.
+	#[rpc(name = "kitties_mintPrice")]
+	fn mint_price(&self, at: Option<BlockHash>) -> Result<Balance>;
+
+	/// Get the number of kitties that can still be created.
+	#[rpc(name = "kitties_remainingSupply")]
+	fn remaining_supply(&self, at: Option<BlockHash>) -> Result<u32>;
+}
+
+/// A struct that implements the `KittiesApi`.
+pub struct Kitties<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Kitties<C, B> {
+	/// Create new `Kitties` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn page_size(count: Option<u32>) -> u32 {
+	count.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE)
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime error".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C, Block, AccountId, KittyIndex, Balance, BlockNumber>
+	KittiesApi<<Block as BlockT>::Hash, AccountId, KittyIndex, Balance, BlockNumber> for Kitties<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: KittiesRuntimeApi<Block, AccountId, KittyIndex, Balance, BlockNumber>,
+	AccountId: Codec,
+	KittyIndex: Codec + Default,
+	Balance: Codec,
+	BlockNumber: Codec,
+{
+	fn kitties_of(
+		&self,
+		owner: AccountId,
+		start: Option<KittyIndex>,
+		count: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<KittyIndex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.kitties_of(&at, owner, start.unwrap_or_default(), page_size(count))
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn kitty(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<KittyInfo<AccountId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.kitty(&at, kitty_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn listed_kitties(
+		&self,
+		start: Option<KittyIndex>,
+		count: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(KittyIndex, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.listed_kitties(&at, start.unwrap_or_default(), page_size(count))
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn current_price(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.current_price(&at, kitty_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn pedigree(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Pedigree<KittyIndex, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.pedigree(&at, kitty_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn ancestors(
+		&self,
+		kitty_id: KittyIndex,
+		depth: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(KittyIndex, u32)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.ancestors(&at, kitty_id, depth).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn mint_price(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Balance> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.mint_price(&at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn remaining_supply(&self, at: Option<<Block as BlockT>::Hash>) -> Result<u32> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.remaining_supply(&at).map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -1,36 +1,187 @@
 use super::*;
 
 use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller, account};
 
+/// Make `count` offers for a kitty from different bidders, each expiring in a different block.
+/// Transfers clear the offers of a kitty, so `MaxOffersPerKitty` offers is their worst case.
+fn make_offers<T: Config>(kitty_id: KittyIndexOf<T>, count: u32) -> DispatchResult {
+	// each offer expires at its own block to stay below `MaxExpiriesPerBlock`
+	let first_expiry = 100 + Offers::<T>::iter().count() as u32;
+	for i in 0..count {
+		let bidder: T::AccountId = account("offerer", i, 0);
+		let _ = T::Currency::make_free_balance_be(&bidder, T::KittyDeposit::get().saturating_add(1000u32.into()));
+		Pallet::<T>::make_offer(RawOrigin::Signed(bidder).into(), kitty_id, 100u32.into(), (first_expiry + i).into())?;
+	}
+	Ok(())
+}
+
 benchmarks! {
 	create {
 		let caller = whitelisted_caller();
+
+		let balance = T::KittyDeposit::get().saturating_add(Pallet::<T>::mint_price()).saturating_add(1000u32.into());
+		let _ = T::Currency::make_free_balance_be(&caller, balance);
 	}: _(RawOrigin::Signed(caller))
 
 	breed {
 		let caller = whitelisted_caller();
 
-		let mut kitty = Kitty(Default::default());
+		let _ = T::Currency::make_free_balance_be(&caller, T::KittyDeposit::get().saturating_add(1000u32.into()));
+
+		let mut kitty = KittyRecord::default();
 		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), kitty.clone())?;
 
-		kitty.0[0] = 1;
+		kitty.dna.0[0] = 1;
 		let kitty_id2 = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), kitty)?;
 
 	}: _(RawOrigin::Signed(caller), kitty_id, kitty_id2)
 
+	offer_siring {
+		let caller = whitelisted_caller();
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+
+	}: _(RawOrigin::Signed(caller), kitty_id, Some(100u32.into()))
+
+	breed_with_sire {
+		let caller = whitelisted_caller();
+		let sire_owner: T::AccountId = account("sire_owner", 0, 0);
+
+		let _ = T::Currency::make_free_balance_be(&caller, T::KittyDeposit::get().saturating_add(1000u32.into()));
+
+		let mut kitty = KittyRecord::default();
+		let sire_id = orml_nft::Pallet::<T>::mint(&sire_owner, Pallet::<T>::class_id(), Vec::new(), kitty.clone())?;
+
+		kitty.dna.0[0] = 1;
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), kitty)?;
+		Pallet::<T>::offer_siring(RawOrigin::Signed(sire_owner).into(), sire_id, Some(100u32.into()))?;
+
+	}: _(RawOrigin::Signed(caller), kitty_id, sire_id, 100u32.into())
+
 	transfer {
 		let caller = whitelisted_caller();
 		let to = account("to", 0, 0);
 
-		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), Kitty(Default::default()))?;
+		let _ = T::Currency::make_free_balance_be(&to, T::KittyDeposit::get().saturating_add(1000u32.into()));
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		make_offers::<T>(kitty_id, T::MaxOffersPerKitty::get())?;
 
 	}: _(RawOrigin::Signed(caller), to, kitty_id)
 
+	batch_transfer {
+		let n in 1 .. T::MaxBatchSize::get();
+
+		let caller = whitelisted_caller();
+		let to = account("to", 0, 0);
+
+		let _ = T::Currency::make_free_balance_be(&to, T::KittyDeposit::get().saturating_mul(n.into()).saturating_add(1000u32.into()));
+
+		let mut kitty_ids = Vec::new();
+		for _ in 0..n {
+			let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+			make_offers::<T>(kitty_id, T::MaxOffersPerKitty::get())?;
+			kitty_ids.push(kitty_id);
+		}
+
+	}: _(RawOrigin::Signed(caller), to, kitty_ids)
+
+	transfer_all {
+		let n in 1 .. T::MaxBatchSize::get();
+
+		let caller = whitelisted_caller();
+		let to = account("to", 0, 0);
+
+		let _ = T::Currency::make_free_balance_be(&to, T::KittyDeposit::get().saturating_mul(n.into()).saturating_add(1000u32.into()));
+
+		for _ in 0..n {
+			let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+			make_offers::<T>(kitty_id, T::MaxOffersPerKitty::get())?;
+		}
+
+	}: _(RawOrigin::Signed(caller.clone()), to)
+	verify {
+		assert!(Pallet::<T>::kitties_of(&caller, Zero::zero(), 1).is_empty());
+	}
+
+	batch_set_price {
+		let n in 1 .. T::MaxBatchSize::get();
+
+		let caller = whitelisted_caller();
+
+		let mut prices = Vec::new();
+		for _ in 0..n {
+			let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+			prices.push((kitty_id, Some(100u32.into())));
+		}
+
+	}: _(RawOrigin::Signed(caller), prices)
+
+	transfer_from {
+		let caller = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let to = account("to", 0, 0);
+
+		let _ = T::Currency::make_free_balance_be(&to, T::KittyDeposit::get().saturating_add(1000u32.into()));
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&owner, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		Pallet::<T>::approve(RawOrigin::Signed(owner.clone()).into(), kitty_id, Some(caller.clone()))?;
+		make_offers::<T>(kitty_id, T::MaxOffersPerKitty::get())?;
+
+	}: _(RawOrigin::Signed(caller), owner, to, kitty_id)
+	verify {
+		assert!(Pallet::<T>::approvals(kitty_id).is_none());
+	}
+
+	approve {
+		let caller: T::AccountId = whitelisted_caller();
+		let spender: T::AccountId = account("spender", 0, 0);
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+
+	}: _(RawOrigin::Signed(caller), kitty_id, Some(spender.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::approvals(kitty_id), Some(spender));
+	}
+
+	set_operator {
+		let caller: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, 0);
+
+	}: _(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+	verify {
+		assert!(Pallet::<T>::is_operator(&caller, &operator));
+	}
+
+	burn {
+		let caller = whitelisted_caller();
+
+		let _ = T::Currency::make_free_balance_be(&Pallet::<T>::account_id(), 1000u32.into());
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		Pallet::<T>::set_price(RawOrigin::Signed(caller.clone()).into(), kitty_id, Some(100u32.into()))?;
+		make_offers::<T>(kitty_id, T::MaxOffersPerKitty::get())?;
+
+	}: _(RawOrigin::Signed(caller), kitty_id)
+
+	set_metadata {
+		let caller = whitelisted_caller();
+
+		let _ = T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		let name = vec![0u8; T::MaxNameLength::get() as usize];
+		let uri = vec![0u8; T::MaxUriLength::get() as usize];
+		Pallet::<T>::set_metadata(RawOrigin::Signed(caller.clone()).into(), kitty_id, name.clone(), uri.clone())?;
+
+	}: _(RawOrigin::Signed(caller), kitty_id, name, uri)
+
 	set_price {
 		let caller = whitelisted_caller();
 
-		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), Kitty(Default::default()))?;
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
 
 	}: _(RawOrigin::Signed(caller), kitty_id, Some(100u32.into()))
 
@@ -38,12 +189,213 @@ benchmarks! {
 		let caller = whitelisted_caller();
 		let seller = account("seller", 0, 0);
 
-		let _ = T::Currency::make_free_balance_be(&caller, 1000u32.into());
+		let _ = T::Currency::make_free_balance_be(&caller, T::KittyDeposit::get().saturating_add(1000u32.into()));
 
-		let kitty_id = orml_nft::Pallet::<T>::mint(&seller, Pallet::<T>::class_id(), Vec::new(), Kitty(Default::default()))?;
+		let kitty_id = orml_nft::Pallet::<T>::mint(&seller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
 		Pallet::<T>::set_price(RawOrigin::Signed(seller.clone()).into(), kitty_id, Some(500u32.into()))?;
+		make_offers::<T>(kitty_id, T::MaxOffersPerKitty::get())?;
 
 	}: _(RawOrigin::Signed(caller), seller, kitty_id, 500u32.into())
+
+	set_royalty {
+		let caller: T::AccountId = whitelisted_caller();
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		KittyCreators::<T>::insert(kitty_id, &caller);
+
+	}: _(RawOrigin::Signed(caller), kitty_id, Some(T::MaxRoyalty::get()))
+
+	list_dutch {
+		let caller = whitelisted_caller();
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		Pallet::<T>::set_price(RawOrigin::Signed(caller.clone()).into(), kitty_id, Some(500u32.into()))?;
+
+	}: _(RawOrigin::Signed(caller), kitty_id, 1000u32.into(), 500u32.into(), 100u32.into())
+
+	make_offer {
+		let caller = whitelisted_caller();
+		let owner = account("owner", 0, 0);
+
+		let _ = T::Currency::make_free_balance_be(&caller, T::KittyDeposit::get().saturating_add(1000u32.into()));
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&owner, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		// the open offers are counted and the expiry block is nearly full
+		make_offers::<T>(kitty_id, T::MaxOffersPerKitty::get().saturating_sub(1))?;
+		let filler: T::AccountId = account("filler", 0, 0);
+		for _ in 1..T::MaxExpiriesPerBlock::get() {
+			OfferExpiryAt::<T>::try_mutate(T::BlockNumber::from(10u32), |expiries| expiries.try_push((kitty_id, filler.clone())))
+				.map_err(|_| "too many expiries")?;
+		}
+
+	}: _(RawOrigin::Signed(caller), kitty_id, 500u32.into(), 10u32.into())
+
+	withdraw_offer {
+		let caller = whitelisted_caller();
+		let owner = account("owner", 0, 0);
+
+		let _ = T::Currency::make_free_balance_be(&caller, T::KittyDeposit::get().saturating_add(1000u32.into()));
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&owner, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		Pallet::<T>::make_offer(RawOrigin::Signed(caller.clone()).into(), kitty_id, 500u32.into(), 10u32.into())?;
+
+	}: _(RawOrigin::Signed(caller), kitty_id)
+
+	accept_offer {
+		let caller = whitelisted_caller();
+		let bidder: T::AccountId = account("bidder", 0, 0);
+
+		let _ = T::Currency::make_free_balance_be(&bidder, T::KittyDeposit::get().saturating_add(1000u32.into()));
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		Pallet::<T>::make_offer(RawOrigin::Signed(bidder.clone()).into(), kitty_id, 500u32.into(), 10u32.into())?;
+		make_offers::<T>(kitty_id, T::MaxOffersPerKitty::get().saturating_sub(1))?;
+
+	}: _(RawOrigin::Signed(caller), kitty_id, bidder)
+
+	expire_offer {
+		let owner = account("owner", 0, 0);
+		let bidder = account("bidder", 0, 0);
+
+		let _ = T::Currency::make_free_balance_be(&bidder, T::KittyDeposit::get().saturating_add(1000u32.into()));
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&owner, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		Pallet::<T>::make_offer(RawOrigin::Signed(bidder).into(), kitty_id, 500u32.into(), 10u32.into())?;
+
+	}: {
+		Pallet::<T>::on_initialize(10u32.into());
+	}
+	verify {
+		assert_eq!(Offers::<T>::iter_prefix(kitty_id).count(), 0);
+	}
+
+	create_auction {
+		let caller = whitelisted_caller();
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		Pallet::<T>::set_price(RawOrigin::Signed(caller.clone()).into(), kitty_id, Some(500u32.into()))?;
+
+	}: _(RawOrigin::Signed(caller), kitty_id, 500u32.into(), 10u32.into(), 10u32.into())
+
+	bid {
+		let caller = whitelisted_caller();
+		let seller = account("seller", 0, 0);
+		let bidder = account("bidder", 0, 0);
+
+		let _ = T::Currency::make_free_balance_be(&caller, T::KittyDeposit::get().saturating_add(2000u32.into()));
+		let _ = T::Currency::make_free_balance_be(&bidder, T::KittyDeposit::get().saturating_add(2000u32.into()));
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&seller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		Pallet::<T>::create_auction(RawOrigin::Signed(seller).into(), kitty_id, 500u32.into(), 10u32.into(), 10u32.into())?;
+		Pallet::<T>::bid(RawOrigin::Signed(bidder).into(), kitty_id, 500u32.into())?;
+
+	}: _(RawOrigin::Signed(caller), kitty_id, 1000u32.into())
+
+	cancel_auction {
+		let caller = whitelisted_caller();
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		Pallet::<T>::create_auction(RawOrigin::Signed(caller.clone()).into(), kitty_id, 500u32.into(), 10u32.into(), 10u32.into())?;
+
+	}: _(RawOrigin::Signed(caller), kitty_id)
+
+	settle_auction {
+		let seller = account("seller", 0, 0);
+		let bidder = account("bidder", 0, 0);
+
+		let _ = T::Currency::make_free_balance_be(&seller, 1000u32.into());
+		let _ = T::Currency::make_free_balance_be(&bidder, T::KittyDeposit::get().saturating_add(2000u32.into()));
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&seller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+		Pallet::<T>::create_auction(RawOrigin::Signed(seller).into(), kitty_id, 500u32.into(), 10u32.into(), 10u32.into())?;
+		Pallet::<T>::bid(RawOrigin::Signed(bidder).into(), kitty_id, 500u32.into())?;
+		make_offers::<T>(kitty_id, T::MaxOffersPerKitty::get())?;
+
+	}: {
+		Pallet::<T>::on_initialize(10u32.into());
+	}
+	verify {
+		assert!(Auctions::<T>::get(kitty_id).is_none());
+	}
+
+	give_birth {
+		let caller: T::AccountId = whitelisted_caller();
+
+		let _ = T::Currency::make_free_balance_be(&caller, T::KittyDeposit::get().saturating_add(1000u32.into()));
+
+		let mut kitty = KittyRecord::default();
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), kitty.clone())?;
+
+		kitty.dna.0[0] = 1;
+		let mother_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), kitty)?;
+		Pallet::<T>::breed(RawOrigin::Signed(caller).into(), kitty_id, mother_id)?;
+		let due = Pallet::<T>::pregnancies(mother_id).unwrap().due;
+
+	}: {
+		Pallet::<T>::on_initialize(due);
+	}
+	verify {
+		assert!(Pallet::<T>::pregnancies(mother_id).is_none());
+	}
+
+	auto_breed {
+		let owner: T::AccountId = account("owner", 0, 0);
+
+		let _ = T::Currency::make_free_balance_be(&owner, T::KittyDeposit::get().saturating_add(1000u32.into()));
+
+		let mut kitty = KittyRecord::default();
+		let kitty_id = orml_nft::Pallet::<T>::mint(&owner, Pallet::<T>::class_id(), Vec::new(), kitty.clone())?;
+
+		kitty.dna.0[0] = 1;
+		let kitty_id2 = orml_nft::Pallet::<T>::mint(&owner, Pallet::<T>::class_id(), Vec::new(), kitty)?;
+
+		Pallet::<T>::set_auto_breed(RawOrigin::Signed(owner.clone()).into(), kitty_id, true)?;
+		Pallet::<T>::set_auto_breed(RawOrigin::Signed(owner).into(), kitty_id2, true)?;
+
+		let solution = (0u128..).find(|solution| Pallet::<T>::validate_solution(kitty_id, kitty_id2, 0, *solution)).unwrap();
+
+	}: _(RawOrigin::None, kitty_id, kitty_id2, 0, solution)
+	verify {
+		assert_eq!(Pallet::<T>::auto_breed_nonce(kitty_id, kitty_id2), 1);
+		assert!(Pallet::<T>::pregnancies(kitty_id2).is_some());
+	}
+
+	set_auto_breed {
+		let caller = whitelisted_caller();
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&caller, Pallet::<T>::class_id(), Vec::new(), KittyRecord::default())?;
+
+	}: _(RawOrigin::Signed(caller), kitty_id, true)
+	verify {
+		assert!(Pallet::<T>::is_auto_breed_enabled(kitty_id));
+	}
+
+	set_mutation_rate {
+		let origin = T::AdminOrigin::successful_origin();
+	}: {
+		Pallet::<T>::set_mutation_rate(origin, Some(Perbill::from_percent(10)))?;
+	}
+	verify {
+		assert_eq!(Pallet::<T>::mutation_rate(), Perbill::from_percent(10));
+	}
+
+	adjust_difficulty {
+		AutoBreedCount::<T>::put(T::TargetAutoBreeds::get().saturating_mul(2));
+	}: {
+		Pallet::<T>::adjust_difficulty();
+	}
+	verify {
+		assert_eq!(Pallet::<T>::auto_breed_count(), 0);
+	}
+
+	set_difficulty {
+		let origin = T::AdminOrigin::successful_origin();
+	}: {
+		Pallet::<T>::set_difficulty(origin, 1000)?;
+	}
+	verify {
+		assert_eq!(Pallet::<T>::difficulty(), 1000);
+	}
 }
 
 impl_benchmark_test_suite!(
@@ -0,0 +1,95 @@
+//! Algorithms combining the dna of two parents into the dna of their kitten.
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+
+use crate::{
+	Kitty,
+	phenotype::{TRAIT_GENES, RECESSIVE_OFFSET},
+};
+
+/// Reasons two kitties cannot breed.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum BreedingError {
+	/// Both kitties have the same gender.
+	SameGender,
+	/// The kitties are incompatible for another reason.
+	Incompatible,
+}
+
+pub trait BreedingStrategy {
+	/// The dna of the kitten of `kitty1` and `kitty2`.
+	/// `seed` is random and the result must only depend on the parents and `seed`.
+	fn breed(kitty1: &Kitty, kitty2: &Kitty, seed: [u8; 16]) -> Result<Kitty, BreedingError>;
+}
+
+fn ensure_different_gender(kitty1: &Kitty, kitty2: &Kitty) -> Result<(), BreedingError> {
+	if kitty1.gender() == kitty2.gender() {
+		return Err(BreedingError::SameGender);
+	}
+	Ok(())
+}
+
+fn combine_dna(dna1: u8, dna2: u8, selector: u8) -> u8 {
+	(!selector & dna1) | (selector & dna2)
+}
+
+fn combine(dna1: &[u8; 16], dna2: &[u8; 16], selector: &[u8; 16]) -> [u8; 16] {
+	let mut new_dna = [0u8; 16];
+	for i in 0..new_dna.len() {
+		new_dna[i] = combine_dna(dna1[i], dna2[i], selector[i]);
+	}
+	new_dna
+}
+
+/// Each bit of the kitten dna is taken from one of the parents, selected by the seed.
+///
+/// This is the default strategy.
+pub struct BitmaskBreeding;
+
+impl BreedingStrategy for BitmaskBreeding {
+	fn breed(kitty1: &Kitty, kitty2: &Kitty, seed: [u8; 16]) -> Result<Kitty, BreedingError> {
+		ensure_different_gender(kitty1, kitty2)?;
+
+		Ok(Kitty(combine(&kitty1.0, &kitty2.0, &seed)))
+	}
+}
+
+/// Each trait gene of the kitten inherits one allele from each parent, selected by the seed.
+/// The allele with the lower trait variant is dominant and becomes visible, the other one is
+/// carried as the recessive allele. Rare traits are therefore only visible when both alleles are
+/// rare.
+///
+/// Genes without a visible trait are mixed as `BitmaskBreeding` does.
+pub struct MendelianBreeding;
+
+impl MendelianBreeding {
+	fn dominates(allele1: u8, allele2: u8) -> bool {
+		allele1 >> 5 <= allele2 >> 5
+	}
+}
+
+impl BreedingStrategy for MendelianBreeding {
+	fn breed(kitty1: &Kitty, kitty2: &Kitty, seed: [u8; 16]) -> Result<Kitty, BreedingError> {
+		ensure_different_gender(kitty1, kitty2)?;
+
+		let (dna1, dna2) = (&kitty1.0, &kitty2.0);
+		let mut new_dna = combine(dna1, dna2, &seed);
+
+		for &gene in TRAIT_GENES.iter() {
+			let recessive = gene + RECESSIVE_OFFSET;
+			let allele1 = if seed[gene] & 1 == 0 { dna1[gene] } else { dna1[recessive] };
+			let allele2 = if seed[gene] & 2 == 0 { dna2[gene] } else { dna2[recessive] };
+
+			let (dominant, other) = if Self::dominates(allele1, allele2) {
+				(allele1, allele2)
+			} else {
+				(allele2, allele1)
+			};
+			new_dna[gene] = dominant;
+			new_dna[recessive] = other;
+		}
+
+		Ok(Kitty(new_dna))
+	}
+}
@@ -2,8 +2,8 @@
 
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Randomness, Currency, ExistenceRequirement},
-	transactional,
+	traits::{Randomness, Currency, ReservableCurrency, ExistenceRequirement, OnUnbalanced, WithdrawReasons},
+	transactional, PalletId,
 };
 use frame_system::{
 	pallet_prelude::*,
@@ -13,8 +13,13 @@ use sp_std::{
 	prelude::*,
 	convert::TryInto
 };
-use sp_io::hashing::blake2_128;
-use sp_runtime::offchain::storage_lock::{StorageLock, BlockAndTime};
+use sp_io::hashing::{blake2_128, blake2_256};
+use sp_runtime::{
+	offchain::storage_lock::{StorageLock, BlockAndTime},
+	traits::{AccountIdConversion, AtLeast32BitUnsigned, One, Saturating, UniqueSaturatedFrom, UniqueSaturatedInto, Zero},
+	helpers_128bit::multiply_by_rational,
+	Perbill, PerThing,
+};
 use rand_chacha::{
 	rand_core::{RngCore, SeedableRng},
 	ChaChaRng,
@@ -29,13 +34,19 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 mod weights;
+mod migrations;
+pub mod phenotype;
+pub mod breeding;
 
 pub use weights::WeightInfo;
+pub use phenotype::Phenotype;
+pub use breeding::{BreedingStrategy, BreedingError, BitmaskBreeding, MendelianBreeding};
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq)]
 pub struct Kitty(pub [u8; 16]);
 
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
 pub enum KittyGender {
 	Male,
@@ -44,30 +55,288 @@ pub enum KittyGender {
 
 impl Kitty {
 	pub fn gender(&self) -> KittyGender {
-		if self.0[0] % 2 == 0 {
-			KittyGender::Male
-		} else {
-			KittyGender::Female
+		phenotype::gender(&self.0)
+	}
+
+	pub fn phenotype(&self) -> Phenotype {
+		Phenotype::decode_dna(&self.0)
+	}
+}
+
+/// How a kitty came into existence.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum KittySource {
+	/// Created by `create` or at genesis.
+	Created,
+	/// Bred by its owner, with or without a sire of another owner.
+	Bred,
+	/// Bred by an `auto_breed` unsigned transaction.
+	AutoBred,
+}
+
+impl Default for KittySource {
+	fn default() -> Self {
+		KittySource::Created
+	}
+}
+
+/// Kitty record, stored as orml_nft token data.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq)]
+pub struct KittyRecord {
+	pub dna: Kitty,
+	pub source: KittySource,
+}
+
+/// Storage version of the pallet.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum Releases {
+	/// Token data is the bare kitty dna.
+	V1,
+	/// Token data is a `KittyRecord`.
+	V2,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1
+	}
+}
+
+/// Kitty metadata, stored SCALE encoded as orml_nft token metadata.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq)]
+pub struct KittyMetadata {
+	pub name: Vec<u8>,
+	/// URI of the kitty image or external resource.
+	pub uri: Vec<u8>,
+}
+
+/// Kitty details exposed through the runtime API.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct KittyInfo<AccountId, Balance> {
+	pub owner: AccountId,
+	pub dna: Kitty,
+	pub gender: KittyGender,
+	pub phenotype: Phenotype,
+	/// None means not for sale.
+	pub price: Option<Balance>,
+	pub metadata: Option<KittyMetadata>,
+}
+
+/// An english auction for a kitty.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Auction<AccountId, Balance, BlockNumber> {
+	/// The owner of the kitty.
+	pub seller: AccountId,
+	/// The minimum first bid.
+	pub reserve: Balance,
+	/// The minimum amount a bid must exceed the current top bid by.
+	pub min_increment: Balance,
+	/// The auction is settled at the beginning of this block.
+	pub end: BlockNumber,
+	/// The current top bidder, bid amount and the kitty deposit reserved with the bid.
+	pub bid: Option<(AccountId, Balance, Balance)>,
+}
+
+/// A dutch listing for a kitty. The price decays linearly from `start_price`
+/// to `end_price` over `duration` blocks and stays at `end_price` afterwards.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct DutchListing<Balance, BlockNumber> {
+	pub start_price: Balance,
+	pub end_price: Balance,
+	/// The block the listing is created at.
+	pub start: BlockNumber,
+	pub duration: BlockNumber,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy, BlockNumber: AtLeast32BitUnsigned + Copy> DutchListing<Balance, BlockNumber> {
+	/// The price at block `now`.
+	pub fn price_at(&self, now: BlockNumber) -> Balance {
+		let elapsed = now.saturating_sub(self.start);
+		if elapsed >= self.duration {
+			return self.end_price;
 		}
+
+		let total_drop: u128 = self.start_price.saturating_sub(self.end_price).unique_saturated_into();
+		let drop = multiply_by_rational(
+			total_drop,
+			elapsed.unique_saturated_into(),
+			self.duration.unique_saturated_into(),
+		).unwrap_or(total_drop);
+
+		self.start_price.saturating_sub(Balance::unique_saturated_from(drop))
 	}
 }
 
+/// An offer to buy a kitty. The amount and the deposit are reserved from the bidder.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Offer<Balance, BlockNumber> {
+	pub amount: Balance,
+	/// The kitty deposit held by the bidder once the offer is accepted.
+	pub deposit: Balance,
+	/// The offer expires at the beginning of this block.
+	pub expiry: BlockNumber,
+}
+
+/// The lineage of a kitty.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Pedigree<KittyIndex, BlockNumber> {
+	/// The parents of a bred kitty. None for a created kitty.
+	pub parents: Option<(KittyIndex, KittyIndex)>,
+	/// 0 for a created kitty, otherwise one more than the highest parent generation.
+	pub generation: u32,
+	/// The block the kitty is born at.
+	pub birth: BlockNumber,
+}
+
+/// A pregnant kitty waiting to give birth.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Pregnancy<AccountId, KittyIndex, Balance, BlockNumber> {
+	/// The owner of the kitten.
+	pub owner: AccountId,
+	/// The owner of the mother when it was bred.
+	pub mother_owner: AccountId,
+	/// The owner of the father when it was bred.
+	pub father_owner: AccountId,
+	/// The kitty deposit reserved from the owner for the kitten.
+	pub deposit: Balance,
+	/// The parents, in breeding order.
+	pub parents: (KittyIndex, KittyIndex),
+	/// The dna of the parents, in breeding order.
+	pub dna: (Kitty, Kitty),
+	pub source: KittySource,
+	/// The kitten is born at the beginning of this block.
+	pub due: BlockNumber,
+}
+
+/// Breeding history of a kitty.
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq)]
+pub struct BreedingState<BlockNumber> {
+	/// The number of times the kitty has bred.
+	pub breed_count: u32,
+	/// The kitty cannot breed before this block.
+	pub cooldown_end: BlockNumber,
+}
+
+/// The maximum depth of an ancestry query.
+pub const MAX_ANCESTRY_DEPTH: u32 = 8;
+
+/// The maximum factor the auto breed difficulty changes by in one adjustment.
+pub const MAX_DIFFICULTY_ADJUSTMENT: u32 = 4;
+
+/// `InvalidTransaction::Custom` code of an auto breed whose kitten owner cannot hold another
+/// kitty.
+pub const KITTEN_OWNER_CANNOT_HOLD: u8 = 1;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config + orml_nft::Config<TokenData = Kitty, ClassData = ()> + SendTransactionTypes<Call<Self>> {
+	pub trait Config: frame_system::Config + orml_nft::Config<TokenData = KittyRecord, ClassData = ()> + SendTransactionTypes<Call<Self>> {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
-		type Currency: Currency<Self::AccountId>;
+		/// The algorithm combining the dna of parents.
+		type Breeding: BreedingStrategy;
+		type Currency: ReservableCurrency<Self::AccountId>;
 		type WeightInfo: WeightInfo;
+		/// The initial auto breed difficulty.
 		#[pallet::constant]
 		type DefaultDifficulty: Get<u32>;
+		/// The number of blocks between adjustments of the auto breed difficulty. Zero disables
+		/// the adjustment.
+		#[pallet::constant]
+		type DifficultyAdjustmentPeriod: Get<Self::BlockNumber>;
+		/// The number of auto breeds per adjustment period the difficulty is adjusted towards.
+		#[pallet::constant]
+		type TargetAutoBreeds: Get<u32>;
+		/// The maximum number of open offers for a kitty.
+		#[pallet::constant]
+		type MaxOffersPerKitty: Get<u32>;
+		/// The maximum number of offers expiring, or auctions ending, in the same block.
+		#[pallet::constant]
+		type MaxExpiriesPerBlock: Get<u32>;
+		/// The share of a secondary sale paid to the creator of the kitty.
+		#[pallet::constant]
+		type DefaultRoyalty: Get<Perbill>;
+		/// The maximum royalty a creator can set for a kitty.
+		#[pallet::constant]
+		type MaxRoyalty: Get<Perbill>;
+		/// The cooldown in blocks after a kitty breeds, indexed by the number of times it has bred.
+		/// The last entry applies to all further breeding.
+		#[pallet::constant]
+		type BreedingCooldowns: Get<Vec<Self::BlockNumber>>;
+		/// The number of blocks from breeding until the kitten is born. At least 1.
+		#[pallet::constant]
+		type GestationPeriod: Get<Self::BlockNumber>;
+		/// The maximum number of times a kitty can breed. None means unlimited.
+		#[pallet::constant]
+		type MaxBreedCount: Get<Option<u32>>;
+		/// The pallet id, used for deriving the account paying burn refunds.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// The amount refunded from the pallet account to the owner of a burned kitty.
+		#[pallet::constant]
+		type BurnRefund: Get<BalanceOf<Self>>;
+		/// The maximum length of a kitty name.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+		/// The maximum length of a kitty URI.
+		#[pallet::constant]
+		type MaxUriLength: Get<u32>;
+		/// The base deposit reserved for setting kitty metadata.
+		#[pallet::constant]
+		type MetadataDepositBase: Get<BalanceOf<Self>>;
+		/// The additional deposit reserved per byte of kitty name and URI.
+		#[pallet::constant]
+		type MetadataDepositPerByte: Get<BalanceOf<Self>>;
+		/// The maximum number of kitties created by `create` or at genesis.
+		#[pallet::constant]
+		type MaxGen0Supply: Get<u32>;
+		/// The price of creating the first kitty.
+		#[pallet::constant]
+		type Gen0BasePrice: Get<BalanceOf<Self>>;
+		/// The increase of the creation price per created kitty.
+		#[pallet::constant]
+		type Gen0PriceIncrement: Get<BalanceOf<Self>>;
+		/// Handler for the payments of created kitties.
+		type OnMintPayment: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// The deposit reserved from the owner of each kitty.
+		#[pallet::constant]
+		type KittyDeposit: Get<BalanceOf<Self>>;
+		/// The maximum number of kitties an account can hold, including unborn kittens.
+		#[pallet::constant]
+		type MaxKittiesPerAccount: Get<u32>;
+		/// The maximum number of kitties in a batch call.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+		/// The probability of a gene of a kitten to mutate, unless overridden by `AdminOrigin`.
+		#[pallet::constant]
+		type DefaultMutationRate: Get<Perbill>;
+		/// The origin allowed to change breeding parameters.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
 	}
 
 	pub type KittyIndexOf<T> = <T as orml_nft::Config>::TokenId;
 	pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+	pub type DutchListingOf<T> = DutchListing<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+	pub type OfferOf<T> = Offer<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+	pub type PedigreeOf<T> = Pedigree<KittyIndexOf<T>, <T as frame_system::Config>::BlockNumber>;
+	pub type PregnancyOf<T> = Pregnancy<
+		<T as frame_system::Config>::AccountId,
+		KittyIndexOf<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub type AuctionOf<T> = Auction<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
 
 	/// Get kitty price. None means not for sale.
 	#[pallet::storage]
@@ -78,27 +347,267 @@ pub mod pallet {
 		BalanceOf<T>, OptionQuery
 	>;
 
+	/// Get the fee to breed with a kitty as sire. None means not offered.
+	#[pallet::storage]
+	#[pallet::getter(fn siring_fees)]
+	pub type SiringFees<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		BalanceOf<T>, OptionQuery
+	>;
+
+	/// Get kitty dutch listing. An alternative to a fixed price.
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_listings)]
+	pub type DutchListings<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		DutchListingOf<T>, OptionQuery
+	>;
+
+	/// Kitties with a fixed price or a dutch listing, keyed by `listing_key` to iterate them in
+	/// ascending order of id.
+	#[pallet::storage]
+	pub type ListedKitties<T: Config> = StorageMap<
+		_,
+		Identity, [u8; 16],
+		KittyIndexOf<T>, OptionQuery
+	>;
+
+	/// Get kitty auction.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		AuctionOf<T>, OptionQuery
+	>;
+
+	/// Index of auctions by end block, used for settlement.
+	#[pallet::storage]
+	pub type AuctionEndAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat, T::BlockNumber,
+		BoundedVec<KittyIndexOf<T>, T::MaxExpiriesPerBlock>, ValueQuery
+	>;
+
+	/// Get offers for a kitty by bidder.
+	#[pallet::storage]
+	#[pallet::getter(fn offers)]
+	pub type Offers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		Blake2_128Concat, T::AccountId,
+		OfferOf<T>, OptionQuery
+	>;
+
+	/// Index of offers by expiry block, used for unreserving expired offers.
+	#[pallet::storage]
+	pub type OfferExpiryAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat, T::BlockNumber,
+		BoundedVec<(KittyIndexOf<T>, T::AccountId), T::MaxExpiriesPerBlock>, ValueQuery
+	>;
+
+	/// Get the account approved to manage a kitty for its owner.
+	#[pallet::storage]
+	#[pallet::getter(fn approvals)]
+	pub type Approvals<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		T::AccountId, OptionQuery
+	>;
+
+	/// Operators approved to manage all kitties of an owner, by owner and operator.
+	#[pallet::storage]
+	pub type Operators<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat, T::AccountId,
+		Blake2_128Concat, T::AccountId,
+		(), OptionQuery
+	>;
+
+	/// Get kitty pedigree.
+	#[pallet::storage]
+	#[pallet::getter(fn pedigrees)]
+	pub type Pedigrees<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		PedigreeOf<T>, OptionQuery
+	>;
+
+	/// Get the pregnancy of a kitty, by the id of the mother.
+	#[pallet::storage]
+	#[pallet::getter(fn pregnancies)]
+	pub type Pregnancies<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		PregnancyOf<T>, OptionQuery
+	>;
+
+	/// Index of pregnancies by due block, used for giving birth.
+	#[pallet::storage]
+	pub type BirthsAt<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat, T::BlockNumber,
+		Blake2_128Concat, KittyIndexOf<T>,
+		(), OptionQuery
+	>;
+
+	/// Get kitty breeding state.
+	#[pallet::storage]
+	#[pallet::getter(fn breeding_states)]
+	pub type BreedingStates<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		BreedingState<T::BlockNumber>, ValueQuery
+	>;
+
+	/// Get the account and amount of the deposit reserved for holding a kitty.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_deposits)]
+	pub type KittyDeposits<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		(T::AccountId, BalanceOf<T>), OptionQuery
+	>;
+
+	/// Get the number of kitties held by an account, including unborn kittens.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_count)]
+	pub type KittyCount<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, T::AccountId,
+		u32, ValueQuery
+	>;
+
+	/// Get the account and amount of the deposit reserved for kitty metadata.
+	#[pallet::storage]
+	#[pallet::getter(fn metadata_deposits)]
+	pub type MetadataDeposits<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		(T::AccountId, BalanceOf<T>), OptionQuery
+	>;
+
+	/// Get the account that minted a kitty. Royalties are paid to this account.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_creators)]
+	pub type KittyCreators<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		T::AccountId, OptionQuery
+	>;
+
+	/// Get the royalty of a kitty set by its creator. None means `DefaultRoyalty`.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_royalties)]
+	pub type KittyRoyalties<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		Perbill, OptionQuery
+	>;
+
+	/// The probability of a gene of a kitten to mutate. None means `DefaultMutationRate`.
+	#[pallet::storage]
+	pub type MutationRate<T: Config> = StorageValue<_, Perbill, OptionQuery>;
+
+	/// Get the number of kitties created by `create` or at genesis.
+	#[pallet::storage]
+	#[pallet::getter(fn gen0_minted)]
+	pub type Gen0Minted<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	/// The class id for orml_nft
 	#[pallet::storage]
 	#[pallet::getter(fn class_id)]
 	pub type ClassId<T: Config> = StorageValue<_, T::ClassId, ValueQuery>;
 
-	/// Nonce for auto breed to prevent replay attack
+	/// Nonce for auto breed of a pair of kitties to prevent replay attack, by the pair in
+	/// ascending order of id.
+	#[pallet::storage]
+	pub type AutoBreedNonces<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, (KittyIndexOf<T>, KittyIndexOf<T>),
+		u32, ValueQuery
+	>;
+
+	/// Kitties opted in to auto breeding, by position in the index.
+	#[pallet::storage]
+	pub type AutoBreedKitties<T: Config> = StorageMap<
+		_,
+		Twox64Concat, u32,
+		KittyIndexOf<T>, OptionQuery
+	>;
+
+	/// Position of a kitty opted in to auto breeding in `AutoBreedKitties`.
 	#[pallet::storage]
-	#[pallet::getter(fn auto_breed_nonce)]
-	pub type AutoBreedNonce<T: Config> = StorageValue<_, u32, ValueQuery>;
+	pub type AutoBreedPositions<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, KittyIndexOf<T>,
+		u32, OptionQuery
+	>;
+
+	/// Get the number of kitties opted in to auto breeding.
+	#[pallet::storage]
+	#[pallet::getter(fn auto_breed_kitty_count)]
+	pub type AutoBreedKittyCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The auto breed difficulty. None means `DefaultDifficulty`.
+	#[pallet::storage]
+	pub type Difficulty<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+	/// Get the number of auto breeds in the current difficulty adjustment period.
+	#[pallet::storage]
+	#[pallet::getter(fn auto_breed_count)]
+	pub type AutoBreedCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Storage version of the pallet. New chains start at the latest version.
+	#[pallet::storage]
+	pub(crate) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
 
 	#[pallet::genesis_config]
-	#[derive(Default)]
-	pub struct GenesisConfig;
+	pub struct GenesisConfig<T: Config> {
+		/// Kitties minted at genesis. \[owner, dna, price\]
+		pub kitties: Vec<(T::AccountId, [u8; 16], Option<BalanceOf<T>>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			GenesisConfig { kitties: Vec::new() }
+		}
+	}
 
 	#[pallet::genesis_build]
-	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
 			// create a NTF class
 			let class_id = orml_nft::Pallet::<T>::create_class(&Default::default(), Vec::new(), ())
 				.expect("Cannot fail or invalid chain spec");
 			ClassId::<T>::put(class_id);
+			StorageVersion::<T>::put(Releases::V2);
+
+			for (owner, dna, price) in &self.kitties {
+				let kitty_id = orml_nft::Pallet::<T>::mint(owner, class_id, Vec::new(), KittyRecord {
+					dna: Kitty(*dna),
+					source: KittySource::Created,
+				})
+					.expect("Cannot fail or invalid chain spec");
+
+				// genesis kitties hold no deposit and are free
+				Gen0Minted::<T>::mutate(|minted| *minted = minted.saturating_add(1));
+				KittyCount::<T>::mutate(owner, |count| *count = count.saturating_add(1));
+				KittyCreators::<T>::insert(kitty_id, owner);
+				Pedigrees::<T>::insert(kitty_id, Pedigree {
+					parents: None,
+					generation: 0,
+					birth: Zero::zero(),
+				});
+				if let Some(price) = price {
+					KittyPrices::<T>::insert(kitty_id, price);
+					ListedKitties::<T>::insert(Pallet::<T>::listing_key(kitty_id), kitty_id);
+				}
+			}
 		}
 	}
 
@@ -106,18 +615,71 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	#[pallet::metadata(
 		T::AccountId = "AccountId", KittyIndexOf<T> = "KittyIndex", Option<BalanceOf<T>> = "Option<Balance>", BalanceOf<T> = "Balance",
+		T::BlockNumber = "BlockNumber", Option<Perbill> = "Option<Perbill>", Option<T::AccountId> = "Option<AccountId>",
 	)]
 	pub enum Event<T: Config> {
 		/// A kitty is created. \[owner, kitty_id, kitty\]
 		KittyCreated(T::AccountId, KittyIndexOf<T>, Kitty),
-		/// A new kitten is bred. \[owner, kitty_id, kitty\]
-		KittyBred(T::AccountId, KittyIndexOf<T>, Kitty),
+		/// A new kitten is born. \[owner, kitty_id, kitty, mother_owner, father_owner\]
+		/// Breeding only emits `Pregnant`, this is emitted when the kitten is born after the
+		/// gestation period.
+		KittyBred(T::AccountId, KittyIndexOf<T>, Kitty, T::AccountId, T::AccountId),
 		/// A kitty is transferred. \[from, to, kitty_id\]
 		KittyTransferred(T::AccountId, T::AccountId, KittyIndexOf<T>),
 		/// The price for a kitty is updated. \[owner, kitty_id, price\]
 		KittyPriceUpdated(T::AccountId, KittyIndexOf<T>, Option<BalanceOf<T>>),
 		/// A kitty is sold. \[old_owner, new_owner, kitty_id, price\]
 		KittySold(T::AccountId, T::AccountId, KittyIndexOf<T>, BalanceOf<T>),
+		/// A kitty is pregnant. \[owner, mother_id, mother_owner, father_id, father_owner, due\]
+		Pregnant(T::AccountId, KittyIndexOf<T>, T::AccountId, KittyIndexOf<T>, T::AccountId, T::BlockNumber),
+		/// A pregnant kitty failed to give birth. \[owner, mother_id\]
+		BirthFailed(T::AccountId, KittyIndexOf<T>),
+		/// Genes of a new kitten mutated. \[kitty_id, genes\]
+		KittyMutated(KittyIndexOf<T>, Vec<u8>),
+		/// The mutation rate is updated. \[mutation_rate\]
+		MutationRateUpdated(Option<Perbill>),
+		/// The auto breed difficulty is updated. \[difficulty\]
+		DifficultyUpdated(u32),
+		/// A kitty is opted in or out of auto breeding. \[owner, kitty_id, enabled\]
+		AutoBreedSet(T::AccountId, KittyIndexOf<T>, bool),
+		/// A kitty is burned. \[owner, kitty_id, refund\]
+		KittyBurned(T::AccountId, KittyIndexOf<T>, BalanceOf<T>),
+		/// The metadata of a kitty is set. \[owner, kitty_id, deposit\]
+		KittyMetadataSet(T::AccountId, KittyIndexOf<T>, BalanceOf<T>),
+		/// The metadata of a kitty is cleared. \[owner, kitty_id\]
+		KittyMetadataCleared(T::AccountId, KittyIndexOf<T>),
+		/// The approved account of a kitty is updated. \[owner, kitty_id, spender\]
+		KittyApproved(T::AccountId, KittyIndexOf<T>, Option<T::AccountId>),
+		/// An operator is approved or revoked for all kitties of an owner. \[owner, operator, approved\]
+		OperatorSet(T::AccountId, T::AccountId, bool),
+		/// The royalty for a kitty is updated. \[creator, kitty_id, royalty\]
+		RoyaltyUpdated(T::AccountId, KittyIndexOf<T>, Option<Perbill>),
+		/// A royalty is paid to the creator of a sold kitty. \[creator, kitty_id, amount\]
+		RoyaltyPaid(T::AccountId, KittyIndexOf<T>, BalanceOf<T>),
+		/// The siring fee for a kitty is updated. \[owner, kitty_id, fee\]
+		SiringOffered(T::AccountId, KittyIndexOf<T>, Option<BalanceOf<T>>),
+		/// A kitty is bred with a sire of another owner. \[breeder, sire_owner, sire_id, fee\]
+		SiringUsed(T::AccountId, T::AccountId, KittyIndexOf<T>, BalanceOf<T>),
+		/// A kitty is listed with a descending price. \[owner, kitty_id, start_price, end_price, duration\]
+		KittyDutchListed(T::AccountId, KittyIndexOf<T>, BalanceOf<T>, BalanceOf<T>, T::BlockNumber),
+		/// An offer is made for a kitty. \[bidder, kitty_id, amount, expiry\]
+		OfferMade(T::AccountId, KittyIndexOf<T>, BalanceOf<T>, T::BlockNumber),
+		/// An offer is withdrawn. \[bidder, kitty_id\]
+		OfferWithdrawn(T::AccountId, KittyIndexOf<T>),
+		/// An offer is accepted and the kitty is sold. \[old_owner, new_owner, kitty_id, amount\]
+		OfferAccepted(T::AccountId, T::AccountId, KittyIndexOf<T>, BalanceOf<T>),
+		/// An offer expired. \[bidder, kitty_id\]
+		OfferExpired(T::AccountId, KittyIndexOf<T>),
+		/// An auction is created. \[seller, kitty_id, reserve, min_increment, end\]
+		AuctionCreated(T::AccountId, KittyIndexOf<T>, BalanceOf<T>, BalanceOf<T>, T::BlockNumber),
+		/// A bid is placed. \[bidder, kitty_id, amount\]
+		AuctionBid(T::AccountId, KittyIndexOf<T>, BalanceOf<T>),
+		/// An auction without bids is cancelled. \[seller, kitty_id\]
+		AuctionCancelled(T::AccountId, KittyIndexOf<T>),
+		/// An auction ended and the kitty is sold to the top bidder. \[seller, winner, kitty_id, amount\]
+		AuctionSold(T::AccountId, T::AccountId, KittyIndexOf<T>, BalanceOf<T>),
+		/// An auction ended without a sale. \[seller, kitty_id\]
+		AuctionExpired(T::AccountId, KittyIndexOf<T>),
 	}
 
 	#[pallet::error]
@@ -128,6 +690,47 @@ pub mod pallet {
 		NotForSale,
 		PriceTooLow,
 		BuyFromSelf,
+		InvalidDutchListing,
+		NameTooLong,
+		UriTooLong,
+		MetadataTooLong,
+		SireNotOffered,
+		FeeTooLow,
+		NotCreator,
+		RoyaltyTooHigh,
+		OfferNotFound,
+		InvalidOfferExpiry,
+		TooManyOffers,
+		KittyOnCooldown,
+		BreedLimitReached,
+		KittyInAuction,
+		AuctionNotFound,
+		InvalidAuctionEnd,
+		AuctionEnded,
+		BidTooLow,
+		BidOnOwnAuction,
+		AuctionHasBids,
+		IncompatibleKitties,
+		KittyPregnant,
+		TooManyKitties,
+		Gen0SupplyExhausted,
+		BatchTooLarge,
+		InvalidSolution,
+		InvalidNonce,
+		InvalidDifficulty,
+		AutoBreedNotEnabled,
+		InvalidOfferAmount,
+		TooManyExpiries,
+		SireNotMale,
+	}
+
+	impl<T> From<BreedingError> for Error<T> {
+		fn from(error: BreedingError) -> Self {
+			match error {
+				BreedingError::SameGender => Error::<T>::SameGender,
+				BreedingError::Incompatible => Error::<T>::IncompatibleKitties,
+			}
+		}
 	}
 
 	#[pallet::pallet]
@@ -136,6 +739,55 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut weight = T::DbWeight::get().reads(1);
+
+			for kitty_id in AuctionEndAt::<T>::take(now).iter() {
+				if let Some(auction) = Auctions::<T>::take(kitty_id) {
+					Self::settle_auction(*kitty_id, auction);
+				}
+				weight = weight.saturating_add(T::WeightInfo::settle_auction());
+			}
+
+			// births are paid for by the breeding calls, so one block of breeds bounds the births due
+			for (mother_id, _) in BirthsAt::<T>::drain_prefix(now) {
+				if let Some(pregnancy) = Pregnancies::<T>::take(mother_id) {
+					Self::give_birth(mother_id, pregnancy);
+				}
+				weight = weight.saturating_add(T::WeightInfo::give_birth());
+			}
+
+			for (kitty_id, bidder) in OfferExpiryAt::<T>::take(now).iter() {
+				if let Some(offer) = Offers::<T>::take(kitty_id, bidder) {
+					T::Currency::unreserve(bidder, offer.amount.saturating_add(offer.deposit));
+					Self::deposit_event(Event::OfferExpired(bidder.clone(), *kitty_id));
+				}
+				weight = weight.saturating_add(T::WeightInfo::expire_offer());
+			}
+
+			let period = T::DifficultyAdjustmentPeriod::get();
+			if !period.is_zero() && (now % period).is_zero() {
+				Self::adjust_difficulty();
+				weight = weight.saturating_add(T::WeightInfo::adjust_difficulty());
+			}
+
+			weight
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate_to_v2::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migrations::pre_migrate_to_v2::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migrations::post_migrate_to_v2::<T>()
+		}
+
 		fn offchain_worker(_now: T::BlockNumber) {
 			let _ = Self::run_offchain_worker();
 		}
@@ -145,15 +797,35 @@ pub mod pallet {
 	impl<T:Config> Pallet<T> {
 
 		/// Create a new kitty
+		/// The caller pays the mint price, which increases with each created kitty
 		#[pallet::weight(T::WeightInfo::create())]
+		#[transactional]
 		pub fn create(origin: OriginFor<T>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
+			let minted = Self::gen0_minted();
+			ensure!(minted < T::MaxGen0Supply::get(), Error::<T>::Gen0SupplyExhausted);
+
+			let payment = T::Currency::withdraw(&sender, Self::mint_price(), WithdrawReasons::TRANSFER, ExistenceRequirement::KeepAlive)?;
+			T::OnMintPayment::on_unbalanced(payment);
+			Gen0Minted::<T>::put(minted + 1);
+
+			let deposit = Self::hold_kitty(&sender)?;
 			let dna = Self::random_value(&sender);
 
 			// Create and store kitty
 			let kitty = Kitty(dna);
-			let kitty_id = orml_nft::Pallet::<T>::mint(&sender, Self::class_id(), Vec::new(), kitty.clone())?;
+			let kitty_id = orml_nft::Pallet::<T>::mint(&sender, Self::class_id(), Vec::new(), KittyRecord {
+				dna: kitty.clone(),
+				source: KittySource::Created,
+			})?;
+			KittyDeposits::<T>::insert(kitty_id, (sender.clone(), deposit));
+			KittyCreators::<T>::insert(kitty_id, &sender);
+			Pedigrees::<T>::insert(kitty_id, Pedigree {
+				parents: None,
+				generation: 0,
+				birth: frame_system::Pallet::<T>::block_number(),
+			});
 
 			// Emit event
 			Self::deposit_event(Event::KittyCreated(sender, kitty_id, kitty));
@@ -162,13 +834,21 @@ pub mod pallet {
 		}
 
 		/// Breed kitties
-		#[pallet::weight(T::WeightInfo::breed())]
+		/// Both kitties must have the same owner, who gets the kitten
+		/// The weight includes the birth of the kitten in `on_initialize`
+		#[pallet::weight(T::WeightInfo::breed().saturating_add(T::WeightInfo::give_birth()))]
 		pub fn breed(origin: OriginFor<T>, kitty_id_1: KittyIndexOf<T>, kitty_id_2: KittyIndexOf<T>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			let kitty1 = Self::kitties(&sender, kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
-			let kitty2 = Self::kitties(&sender, kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+			let (owner, kitty1) = Self::approved_kitty(&sender, kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
+			let kitty2 = Self::approved_kitty(&sender, kitty_id_2)
+				.filter(|(owner2, _)| *owner2 == owner)
+				.map(|(_, kitty)| kitty)
+				.ok_or(Error::<T>::InvalidKittyId)?;
+
+			Self::ensure_unlocked(kitty_id_1)?;
+			Self::ensure_unlocked(kitty_id_2)?;
 
-			Self::do_breed(sender, kitty1, kitty2)
+			Self::do_breed(owner.clone(), (owner.clone(), owner), kitty_id_1, kitty1, kitty_id_2, kitty2, KittySource::Bred)
 		}
 
 		/// Transfer a kitty to new owner
@@ -176,30 +856,17 @@ pub mod pallet {
 		pub fn transfer(origin: OriginFor<T>, to: T::AccountId, kitty_id: KittyIndexOf<T>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			orml_nft::Pallet::<T>::transfer(&sender, &to, (Self::class_id(), kitty_id))?;
-
-			if sender != to {
-				KittyPrices::<T>::remove(kitty_id);
-
-				Self::deposit_event(Event::KittyTransferred(sender, to, kitty_id));
-			}
-
-			Ok(())
+			Self::transfer_kitty(&sender, &to, kitty_id)
 		}
 
 		/// Set a price for a kitty for sale
  		/// None to delist the kitty
+		/// Replaces any dutch listing of the kitty
 		#[pallet::weight(T::WeightInfo::set_price())]
 		pub fn set_price(origin: OriginFor<T>, kitty_id: KittyIndexOf<T>, new_price: Option<BalanceOf<T>>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			ensure!(orml_nft::TokensByOwner::<T>::contains_key(&sender, (Self::class_id(), kitty_id)), Error::<T>::NotOwner);
-
-			KittyPrices::<T>::mutate_exists(kitty_id, |price| *price = new_price);
-
-			Self::deposit_event(Event::KittyPriceUpdated(sender, kitty_id, new_price));
-
-			Ok(())
+			Self::set_kitty_price(&sender, kitty_id, new_price)
 		}
 
 		/// Buy a kitty
@@ -210,74 +877,941 @@ pub mod pallet {
 
 			ensure!(sender != owner, Error::<T>::BuyFromSelf);
 
-			KittyPrices::<T>::try_mutate_exists(kitty_id, |price| -> DispatchResult {
-				let price = price.take().ok_or(Error::<T>::NotForSale)?;
+			let price = Self::current_price(kitty_id).ok_or(Error::<T>::NotForSale)?;
+			Self::ensure_unlocked(kitty_id)?;
 
-				ensure!(max_price >= price, Error::<T>::PriceTooLow);
+			ensure!(max_price >= price, Error::<T>::PriceTooLow);
 
-				orml_nft::Pallet::<T>::transfer(&owner, &sender, (Self::class_id(), kitty_id))?;
-				T::Currency::transfer(&sender, &owner, price, ExistenceRequirement::KeepAlive)?;
+			Self::do_transfer(&owner, &sender, kitty_id)?;
+			Self::pay_for_kitty(&sender, &owner, kitty_id, price, ExistenceRequirement::KeepAlive)?;
 
-				Self::deposit_event(Event::KittySold(owner, sender, kitty_id, price));
+			Self::deposit_event(Event::KittySold(owner, sender, kitty_id, price));
 
-				Ok(())
-			})
+			Ok(())
 		}
 
-		#[pallet::weight(1000)]
-		pub fn auto_breed(origin: OriginFor<T>, kitty_id_1: KittyIndexOf<T>, kitty_id_2: KittyIndexOf<T>, _nonce: u32, _solution: u128) -> DispatchResult {
+		/// Breed two kitties with a proof of work solution, submitted by offchain workers
+		/// Both kitties must be opted in to auto breeding
+		/// The nonce of the pair is advanced so the solution cannot be replayed
+		/// The owners of the pair get the kittens in turns, see `auto_breed_owner`
+		/// The weight includes the birth of the kitten in `on_initialize`
+		#[pallet::weight(T::WeightInfo::auto_breed().saturating_add(T::WeightInfo::give_birth()))]
+		pub fn auto_breed(origin: OriginFor<T>, kitty_id_1: KittyIndexOf<T>, kitty_id_2: KittyIndexOf<T>, nonce: u32, solution: u128) -> DispatchResult {
 			ensure_none(origin)?;
 
-			let kitty1 = orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
-			let kitty2 = orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(Self::validate_solution(kitty_id_1, kitty_id_2, nonce, solution), Error::<T>::InvalidSolution);
+			ensure!(nonce == Self::auto_breed_nonce(kitty_id_1, kitty_id_2), Error::<T>::InvalidNonce);
+
+			let ((owner1, kitty1), (owner2, kitty2)) = Self::ensure_auto_breedable(kitty_id_1, kitty_id_2)?;
+
+			let owner = Self::auto_breed_owner((kitty_id_1, &owner1), (kitty_id_2, &owner2), nonce);
+			Self::do_breed(owner, (owner1, owner2), kitty_id_1, kitty1, kitty_id_2, kitty2, KittySource::AutoBred)?;
+
+			AutoBreedNonces::<T>::insert(Self::auto_breed_pair(kitty_id_1, kitty_id_2), nonce.wrapping_add(1));
+			AutoBreedCount::<T>::mutate(|count| *count = count.saturating_add(1));
 
-			Self::do_breed(kitty1.owner, kitty1.data, kitty2.data)
+			Ok(())
 		}
-	}
 
-	#[pallet::validate_unsigned]
-	impl<T: Config> frame_support::unsigned::ValidateUnsigned for Pallet<T> {
-		type Call = Call<T>;
+		/// Offer a male kitty as sire for other owners to breed with for a fee
+		/// None to withdraw the offer
+		#[pallet::weight(T::WeightInfo::offer_siring())]
+		pub fn offer_siring(origin: OriginFor<T>, kitty_id: KittyIndexOf<T>, fee: Option<BalanceOf<T>>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
 
-		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-			match *call {
-				Call::auto_breed(kitty_id_1, kitty_id_2, nonce, solution) => {
-					if Self::validate_solution(kitty_id_1, kitty_id_2, nonce, solution) {
-						if nonce != Self::auto_breed_nonce() {
-							return InvalidTransaction::BadProof.into();
-						}
+			let (owner, kitty) = Self::approved_kitty(&sender, kitty_id).ok_or(Error::<T>::NotOwner)?;
+			Self::ensure_unlocked(kitty_id)?;
+			ensure!(fee.is_none() || kitty.gender() == KittyGender::Male, Error::<T>::SireNotMale);
 
-						AutoBreedNonce::<T>::mutate(|nonce| *nonce = nonce.saturating_add(1));
+			SiringFees::<T>::mutate_exists(kitty_id, |value| *value = fee);
 
-						ValidTransaction::with_tag_prefix("kitties")
-							.longevity(64_u64)
-							.propagate(true)
-							.build()
-					} else {
-						InvalidTransaction::BadProof.into()
-					}
-				},
-				_ => InvalidTransaction::Call.into(),
-			}
+			Self::deposit_event(Event::SiringOffered(owner, kitty_id, fee));
+
+			Ok(())
 		}
-	}
-}
 
-fn combine_dna(dna1: u8, dna2: u8, selector: u8) -> u8 {
-	(!selector & dna1) | (selector & dna2)
-}
+		/// Breed a kitty with a sire offered by another owner
+		/// The caller pays the siring fee to the sire owner and the kitten belongs to the kitty owner
+		/// The weight includes the birth of the kitten in `on_initialize`
+		#[pallet::weight(T::WeightInfo::breed_with_sire().saturating_add(T::WeightInfo::give_birth()))]
+		#[transactional]
+		pub fn breed_with_sire(
+			origin: OriginFor<T>,
+			kitty_id: KittyIndexOf<T>,
+			sire_id: KittyIndexOf<T>,
+			max_fee: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
 
-impl<T: Config> Pallet<T> {
-	fn kitties(owner: &T::AccountId, kitty_id: KittyIndexOf<T>) -> Option<Kitty> {
-		orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id).and_then(|x| {
-			if x.owner == *owner {
-				Some(x.data)
+			let (owner, kitty) = Self::approved_kitty(&sender, kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			let sire = orml_nft::Pallet::<T>::tokens(Self::class_id(), sire_id).ok_or(Error::<T>::InvalidKittyId)?;
+			let fee = Self::siring_fees(sire_id).ok_or(Error::<T>::SireNotOffered)?;
+
+			ensure!(sire.owner != owner, Error::<T>::BuyFromSelf);
+			ensure!(sire.data.dna.gender() == KittyGender::Male, Error::<T>::SireNotMale);
+			ensure!(max_fee >= fee, Error::<T>::FeeTooLow);
+
+			Self::ensure_unlocked(kitty_id)?;
+			Self::ensure_unlocked(sire_id)?;
+
+			T::Currency::transfer(&sender, &sire.owner, fee, ExistenceRequirement::KeepAlive)?;
+
+			Self::do_breed(owner.clone(), (owner.clone(), sire.owner.clone()), kitty_id, kitty, sire_id, sire.data.dna, KittySource::Bred)?;
+
+			Self::deposit_event(Event::SiringUsed(owner, sire.owner, sire_id, fee));
+
+			Ok(())
+		}
+
+		/// Transfer kitties to new owner
+		/// Nothing is transferred if any of the kitties cannot be transferred
+		#[pallet::weight(T::WeightInfo::batch_transfer(kitty_ids.len() as u32))]
+		#[transactional]
+		pub fn batch_transfer(origin: OriginFor<T>, to: T::AccountId, kitty_ids: Vec<KittyIndexOf<T>>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(kitty_ids.len() as u32 <= T::MaxBatchSize::get(), Error::<T>::BatchTooLarge);
+
+			for kitty_id in kitty_ids {
+				Self::transfer_kitty(&sender, &to, kitty_id)?;
+			}
+
+			Ok(())
+		}
+
+		/// Transfer all kitties of the caller to new owner
+		/// Nothing is transferred if any of the kitties cannot be transferred
+		/// or the caller owns more than `MaxBatchSize` kitties
+		#[pallet::weight(T::WeightInfo::transfer_all(T::MaxBatchSize::get()))]
+		#[transactional]
+		pub fn transfer_all(origin: OriginFor<T>, to: T::AccountId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let max = T::MaxBatchSize::get();
+			let kitty_ids = Self::kitties_of(&sender, Zero::zero(), max.saturating_add(1));
+			ensure!(kitty_ids.len() as u32 <= max, Error::<T>::BatchTooLarge);
+
+			let count = kitty_ids.len() as u32;
+			for kitty_id in kitty_ids {
+				Self::transfer_kitty(&sender, &to, kitty_id)?;
+			}
+
+			Ok(Some(T::WeightInfo::transfer_all(count)).into())
+		}
+
+		/// Transfer a kitty of `from` to new owner
+		/// The caller must be the owner, approved for the kitty or an operator of the owner
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		pub fn transfer_from(origin: OriginFor<T>, from: T::AccountId, to: T::AccountId, kitty_id: KittyIndexOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let (owner, _) = Self::approved_kitty(&sender, kitty_id).ok_or(Error::<T>::NotOwner)?;
+			ensure!(owner == from, Error::<T>::NotOwner);
+
+			Self::transfer_kitty(&from, &to, kitty_id)
+		}
+
+		/// Approve an account to transfer, price and breed a kitty for its owner
+		/// None to remove the approval
+		/// The approval is cleared when the kitty is transferred
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(origin: OriginFor<T>, kitty_id: KittyIndexOf<T>, spender: Option<T::AccountId>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let kitty = orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(sender == kitty.owner || Self::is_operator(&kitty.owner, &sender), Error::<T>::NotOwner);
+
+			Approvals::<T>::mutate_exists(kitty_id, |value| *value = spender.clone());
+
+			Self::deposit_event(Event::KittyApproved(kitty.owner, kitty_id, spender));
+
+			Ok(())
+		}
+
+		/// Approve or revoke an operator to manage all kitties of the caller
+		#[pallet::weight(T::WeightInfo::set_operator())]
+		pub fn set_operator(origin: OriginFor<T>, operator: T::AccountId, approved: bool) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			if approved {
+				Operators::<T>::insert(&sender, &operator, ());
+			} else {
+				Operators::<T>::remove(&sender, &operator);
+			}
+
+			Self::deposit_event(Event::OperatorSet(sender, operator, approved));
+
+			Ok(())
+		}
+
+		/// Burn a kitty
+		/// The owner is refunded `BurnRefund` if the pallet account can pay for it
+		#[pallet::weight(T::WeightInfo::burn())]
+		#[transactional]
+		pub fn burn(origin: OriginFor<T>, kitty_id: KittyIndexOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Self::ensure_unlocked(kitty_id)?;
+
+			orml_nft::Pallet::<T>::burn(&sender, (Self::class_id(), kitty_id))?;
+
+			Self::clear_listings(kitty_id);
+			Self::clear_approval(&sender, kitty_id);
+			KittyCreators::<T>::remove(kitty_id);
+			KittyRoyalties::<T>::remove(kitty_id);
+			BreedingStates::<T>::remove(kitty_id);
+			Self::release_metadata_deposit(kitty_id);
+			Self::release_kitty(&sender, kitty_id);
+			// pedigree is kept so the ancestry of descendants stays intact
+
+			let mut refund = T::BurnRefund::get();
+			if !refund.is_zero() &&
+				T::Currency::transfer(&Self::account_id(), &sender, refund, ExistenceRequirement::KeepAlive).is_err()
+			{
+				refund = Zero::zero();
+			}
+
+			Self::deposit_event(Event::KittyBurned(sender, kitty_id, refund));
+
+			Ok(())
+		}
+
+		/// Set the name and URI of a kitty
+		/// A deposit proportional to the length is reserved from the caller
+		/// Empty name and URI clear the metadata and release the deposit
+		/// The metadata is also cleared when the kitty is transferred
+		#[pallet::weight(T::WeightInfo::set_metadata())]
+		#[transactional]
+		pub fn set_metadata(origin: OriginFor<T>, kitty_id: KittyIndexOf<T>, name: Vec<u8>, uri: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(orml_nft::TokensByOwner::<T>::contains_key(&sender, (Self::class_id(), kitty_id)), Error::<T>::NotOwner);
+			Self::ensure_unlocked(kitty_id)?;
+			ensure!(name.len() <= T::MaxNameLength::get() as usize, Error::<T>::NameTooLong);
+			ensure!(uri.len() <= T::MaxUriLength::get() as usize, Error::<T>::UriTooLong);
+
+			Self::release_metadata_deposit(kitty_id);
+
+			let (encoded, deposit) = if name.is_empty() && uri.is_empty() {
+				(Vec::new(), Zero::zero())
+			} else {
+				let byte_count: BalanceOf<T> = ((name.len() + uri.len()) as u32).into();
+				let deposit = T::MetadataDepositBase::get()
+					.saturating_add(T::MetadataDepositPerByte::get().saturating_mul(byte_count));
+
+				T::Currency::reserve(&sender, deposit)?;
+				MetadataDeposits::<T>::insert(kitty_id, (sender.clone(), deposit));
+
+				(KittyMetadata { name, uri }.encode(), deposit)
+			};
+
+			let is_cleared = encoded.is_empty();
+
+			orml_nft::Tokens::<T>::try_mutate(Self::class_id(), kitty_id, |token| -> DispatchResult {
+				let token = token.as_mut().ok_or(Error::<T>::InvalidKittyId)?;
+				token.metadata = encoded.try_into().map_err(|_| Error::<T>::MetadataTooLong)?;
+				Ok(())
+			})?;
+
+			if is_cleared {
+				Self::deposit_event(Event::KittyMetadataCleared(sender, kitty_id));
+			} else {
+				Self::deposit_event(Event::KittyMetadataSet(sender, kitty_id, deposit));
+			}
+
+			Ok(())
+		}
+
+		/// Set prices for kitties for sale
+		/// None to delist a kitty
+		/// Nothing is updated if any of the prices cannot be set
+		#[pallet::weight(T::WeightInfo::batch_set_price(prices.len() as u32))]
+		#[transactional]
+		pub fn batch_set_price(origin: OriginFor<T>, prices: Vec<(KittyIndexOf<T>, Option<BalanceOf<T>>)>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(prices.len() as u32 <= T::MaxBatchSize::get(), Error::<T>::BatchTooLarge);
+
+			for (kitty_id, new_price) in prices {
+				Self::set_kitty_price(&sender, kitty_id, new_price)?;
+			}
+
+			Ok(())
+		}
+
+		/// Set the royalty paid to the creator on secondary sales of a kitty
+		/// Only the creator can set it, while still owning the kitty
+		/// None to use the default royalty
+		#[pallet::weight(T::WeightInfo::set_royalty())]
+		pub fn set_royalty(origin: OriginFor<T>, kitty_id: KittyIndexOf<T>, royalty: Option<Perbill>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::kitty_creators(kitty_id).as_ref() == Some(&sender), Error::<T>::NotCreator);
+			ensure!(orml_nft::TokensByOwner::<T>::contains_key(&sender, (Self::class_id(), kitty_id)), Error::<T>::NotOwner);
+			ensure!(royalty.map_or(true, |royalty| royalty <= T::MaxRoyalty::get()), Error::<T>::RoyaltyTooHigh);
+
+			KittyRoyalties::<T>::mutate_exists(kitty_id, |value| *value = royalty);
+
+			Self::deposit_event(Event::RoyaltyUpdated(sender, kitty_id, royalty));
+
+			Ok(())
+		}
+
+		/// List a kitty for sale with a linearly descending price
+		/// The price starts at `start_price` and reaches `end_price` after `duration` blocks
+		/// Replaces any fixed price of the kitty
+		#[pallet::weight(T::WeightInfo::list_dutch())]
+		pub fn list_dutch(
+			origin: OriginFor<T>,
+			kitty_id: KittyIndexOf<T>,
+			start_price: BalanceOf<T>,
+			end_price: BalanceOf<T>,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let (owner, _) = Self::approved_kitty(&sender, kitty_id).ok_or(Error::<T>::NotOwner)?;
+			Self::ensure_unlocked(kitty_id)?;
+			ensure!(start_price >= end_price && !duration.is_zero(), Error::<T>::InvalidDutchListing);
+
+			KittyPrices::<T>::remove(kitty_id);
+			DutchListings::<T>::insert(kitty_id, DutchListing {
+				start_price,
+				end_price,
+				start: frame_system::Pallet::<T>::block_number(),
+				duration,
+			});
+			ListedKitties::<T>::insert(Self::listing_key(kitty_id), kitty_id);
+
+			Self::deposit_event(Event::KittyDutchListed(owner, kitty_id, start_price, end_price, duration));
+
+			Ok(())
+		}
+
+		/// Make an offer for a kitty
+		/// The amount and the kitty deposit are reserved until the offer is accepted, withdrawn or expired
+		/// Replaces the previous offer of the bidder for the kitty
+		#[pallet::weight(T::WeightInfo::make_offer())]
+		#[transactional]
+		pub fn make_offer(
+			origin: OriginFor<T>,
+			kitty_id: KittyIndexOf<T>,
+			amount: BalanceOf<T>,
+			expiry: T::BlockNumber,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let kitty = orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(kitty.owner != sender, Error::<T>::BuyFromSelf);
+			ensure!(!amount.is_zero(), Error::<T>::InvalidOfferAmount);
+			ensure!(expiry > frame_system::Pallet::<T>::block_number(), Error::<T>::InvalidOfferExpiry);
+
+			if let Some(offer) = Offers::<T>::take(kitty_id, &sender) {
+				T::Currency::unreserve(&sender, offer.amount.saturating_add(offer.deposit));
+				Self::remove_offer_expiry(offer.expiry, kitty_id, &sender);
+			} else {
+				let offer_count = Offers::<T>::iter_prefix(kitty_id).count() as u32;
+				ensure!(offer_count < T::MaxOffersPerKitty::get(), Error::<T>::TooManyOffers);
+			}
+
+			let deposit = T::KittyDeposit::get();
+			T::Currency::reserve(&sender, amount.saturating_add(deposit))?;
+
+			Offers::<T>::insert(kitty_id, &sender, Offer { amount, deposit, expiry });
+			OfferExpiryAt::<T>::try_mutate(expiry, |expiries| expiries.try_push((kitty_id, sender.clone())))
+				.map_err(|_| Error::<T>::TooManyExpiries)?;
+
+			Self::deposit_event(Event::OfferMade(sender, kitty_id, amount, expiry));
+
+			Ok(())
+		}
+
+		/// Withdraw an offer and unreserve the amount
+		#[pallet::weight(T::WeightInfo::withdraw_offer())]
+		pub fn withdraw_offer(origin: OriginFor<T>, kitty_id: KittyIndexOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let offer = Offers::<T>::take(kitty_id, &sender).ok_or(Error::<T>::OfferNotFound)?;
+			Self::remove_offer_expiry(offer.expiry, kitty_id, &sender);
+			T::Currency::unreserve(&sender, offer.amount.saturating_add(offer.deposit));
+
+			Self::deposit_event(Event::OfferWithdrawn(sender, kitty_id));
+
+			Ok(())
+		}
+
+		/// Accept an offer for a kitty
+		/// The kitty is transferred to the bidder and the offered amount is paid to the owner
+		#[pallet::weight(T::WeightInfo::accept_offer())]
+		#[transactional]
+		pub fn accept_offer(origin: OriginFor<T>, kitty_id: KittyIndexOf<T>, bidder: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(orml_nft::TokensByOwner::<T>::contains_key(&sender, (Self::class_id(), kitty_id)), Error::<T>::NotOwner);
+			Self::ensure_unlocked(kitty_id)?;
+
+			let offer = Offers::<T>::take(kitty_id, &bidder).ok_or(Error::<T>::OfferNotFound)?;
+			Self::remove_offer_expiry(offer.expiry, kitty_id, &bidder);
+
+			// the deposit is reserved again by the transfer
+			T::Currency::unreserve(&bidder, offer.amount.saturating_add(offer.deposit));
+			Self::pay_for_kitty(&bidder, &sender, kitty_id, offer.amount, ExistenceRequirement::AllowDeath)?;
+
+			// remaining offers are invalidated by the transfer
+			Self::do_transfer(&sender, &bidder, kitty_id)?;
+
+			Self::deposit_event(Event::OfferAccepted(sender, bidder, kitty_id, offer.amount));
+
+			Ok(())
+		}
+
+		/// Put a kitty up for an english auction
+		/// The auction is settled at the beginning of `end` block
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			kitty_id: KittyIndexOf<T>,
+			reserve: BalanceOf<T>,
+			min_increment: BalanceOf<T>,
+			end: T::BlockNumber,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(orml_nft::TokensByOwner::<T>::contains_key(&sender, (Self::class_id(), kitty_id)), Error::<T>::NotOwner);
+			Self::ensure_unlocked(kitty_id)?;
+			ensure!(end > frame_system::Pallet::<T>::block_number(), Error::<T>::InvalidAuctionEnd);
+
+			AuctionEndAt::<T>::try_mutate(end, |ends| ends.try_push(kitty_id))
+				.map_err(|_| Error::<T>::TooManyExpiries)?;
+
+			// a kitty in auction cannot have a fixed price
+			KittyPrices::<T>::remove(kitty_id);
+			DutchListings::<T>::remove(kitty_id);
+			ListedKitties::<T>::remove(Self::listing_key(kitty_id));
+
+			Auctions::<T>::insert(kitty_id, Auction {
+				seller: sender.clone(),
+				reserve,
+				min_increment,
+				end,
+				bid: None,
+			});
+
+			Self::deposit_event(Event::AuctionCreated(sender, kitty_id, reserve, min_increment, end));
+
+			Ok(())
+		}
+
+		/// Bid for a kitty in auction
+		/// The bid amount and the kitty deposit are reserved until the bidder is outbid or the auction is settled
+		#[pallet::weight(T::WeightInfo::bid())]
+		#[transactional]
+		pub fn bid(origin: OriginFor<T>, kitty_id: KittyIndexOf<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Auctions::<T>::try_mutate(kitty_id, |auction| -> DispatchResult {
+				let auction = auction.as_mut().ok_or(Error::<T>::AuctionNotFound)?;
+
+				ensure!(sender != auction.seller, Error::<T>::BidOnOwnAuction);
+				ensure!(frame_system::Pallet::<T>::block_number() < auction.end, Error::<T>::AuctionEnded);
+
+				if let Some((bidder, top_bid, deposit)) = auction.bid.take() {
+					ensure!(amount >= top_bid.saturating_add(auction.min_increment), Error::<T>::BidTooLow);
+					T::Currency::unreserve(&bidder, top_bid.saturating_add(deposit));
+				} else {
+					ensure!(amount >= auction.reserve, Error::<T>::BidTooLow);
+				}
+
+				let deposit = T::KittyDeposit::get();
+				T::Currency::reserve(&sender, amount.saturating_add(deposit))?;
+				auction.bid = Some((sender.clone(), amount, deposit));
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::AuctionBid(sender, kitty_id, amount));
+
+			Ok(())
+		}
+
+		/// Cancel an auction that has no bids
+		#[pallet::weight(T::WeightInfo::cancel_auction())]
+		pub fn cancel_auction(origin: OriginFor<T>, kitty_id: KittyIndexOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let auction = Self::auctions(kitty_id).ok_or(Error::<T>::AuctionNotFound)?;
+
+			ensure!(auction.seller == sender, Error::<T>::NotOwner);
+			ensure!(auction.bid.is_none(), Error::<T>::AuctionHasBids);
+
+			Auctions::<T>::remove(kitty_id);
+			AuctionEndAt::<T>::mutate_exists(auction.end, |ends| {
+				if let Some(kitty_ids) = ends {
+					kitty_ids.retain(|id| *id != kitty_id);
+					if kitty_ids.is_empty() {
+						*ends = None;
+					}
+				}
+			});
+
+			Self::deposit_event(Event::AuctionCancelled(sender, kitty_id));
+
+			Ok(())
+		}
+
+		/// Set the probability of a gene of a kitten to mutate
+		/// None to use the default mutation rate
+		#[pallet::weight(T::WeightInfo::set_mutation_rate())]
+		pub fn set_mutation_rate(origin: OriginFor<T>, mutation_rate: Option<Perbill>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			MutationRate::<T>::set(mutation_rate);
+
+			Self::deposit_event(Event::MutationRateUpdated(mutation_rate));
+
+			Ok(())
+		}
+
+		/// Opt a kitty in or out of auto breeding by offchain workers
+		/// The kitty is opted out when it is transferred
+		#[pallet::weight(T::WeightInfo::set_auto_breed())]
+		pub fn set_auto_breed(origin: OriginFor<T>, kitty_id: KittyIndexOf<T>, enabled: bool) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let (owner, _) = Self::approved_kitty(&sender, kitty_id).ok_or(Error::<T>::NotOwner)?;
+
+			if enabled {
+				Self::enable_auto_breed(kitty_id);
+			} else {
+				Self::disable_auto_breed(kitty_id);
+			}
+
+			Self::deposit_event(Event::AutoBreedSet(owner, kitty_id, enabled));
+
+			Ok(())
+		}
+
+		/// Override the auto breed difficulty until the next adjustment
+		#[pallet::weight(T::WeightInfo::set_difficulty())]
+		pub fn set_difficulty(origin: OriginFor<T>, difficulty: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			ensure!(!difficulty.is_zero(), Error::<T>::InvalidDifficulty);
+
+			Difficulty::<T>::put(difficulty);
+
+			Self::deposit_event(Event::DifficultyUpdated(difficulty));
+
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> frame_support::unsigned::ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			match *call {
+				Call::auto_breed(kitty_id_1, kitty_id_2, nonce, solution) => {
+					if !Self::validate_solution(kitty_id_1, kitty_id_2, nonce, solution) {
+						return InvalidTransaction::BadProof.into();
+					}
+
+					if nonce != Self::auto_breed_nonce(kitty_id_1, kitty_id_2) {
+						return InvalidTransaction::Stale.into();
+					}
+
+					// a solution for a pair that cannot breed would never be included
+					let ((owner1, _), (owner2, _)) = match Self::ensure_auto_breedable(kitty_id_1, kitty_id_2) {
+						Ok(pair) => pair,
+						Err(_) => return InvalidTransaction::Call.into(),
+					};
+
+					// neither would one whose kitten owner cannot pay the deposit or is at the limit
+					let owner = Self::auto_breed_owner((kitty_id_1, &owner1), (kitty_id_2, &owner2), nonce);
+					if !Self::can_hold_kitty(&owner) {
+						return InvalidTransaction::Custom(KITTEN_OWNER_CANNOT_HOLD).into();
+					}
+
+					// solutions for the same pair and nonce replace each other in the pool
+					ValidTransaction::with_tag_prefix("kitties")
+						.and_provides((Self::auto_breed_pair(kitty_id_1, kitty_id_2), nonce))
+						.longevity(64_u64)
+						.propagate(true)
+						.build()
+				},
+				_ => InvalidTransaction::Call.into(),
+			}
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The dna of a kitty, if it is owned by `owner`.
+	pub fn kitties(owner: &T::AccountId, kitty_id: KittyIndexOf<T>) -> Option<Kitty> {
+		orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id).and_then(|x| {
+			if x.owner == *owner {
+				Some(x.data.dna)
 			} else {
 				None
 			}
 		})
 	}
 
+	/// The owner and dna of a kitty, if `who` can manage it.
+	fn approved_kitty(who: &T::AccountId, kitty_id: KittyIndexOf<T>) -> Option<(T::AccountId, Kitty)> {
+		orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id).and_then(|x| {
+			if Self::is_approved(who, &x.owner, kitty_id) {
+				Some((x.owner, x.data.dna))
+			} else {
+				None
+			}
+		})
+	}
+
+	/// Whether `who` can manage a kitty of `owner`, as the owner, the approved account of the
+	/// kitty or an operator of the owner.
+	pub fn is_approved(who: &T::AccountId, owner: &T::AccountId, kitty_id: KittyIndexOf<T>) -> bool {
+		who == owner || Self::approvals(kitty_id).as_ref() == Some(who) || Self::is_operator(owner, who)
+	}
+
+	/// Whether `operator` can manage all kitties of `owner`.
+	pub fn is_operator(owner: &T::AccountId, operator: &T::AccountId) -> bool {
+		Operators::<T>::contains_key(owner, operator)
+	}
+
+	/// Ids of the kitties owned by `owner`, in ascending order, starting from `start`.
+	pub fn kitties_of(owner: &T::AccountId, start: KittyIndexOf<T>, count: u32) -> Vec<KittyIndexOf<T>> {
+		let class_id = Self::class_id();
+		let mut kitty_ids = orml_nft::TokensByOwner::<T>::iter_prefix(owner)
+			.filter_map(|((token_class_id, kitty_id), _)| {
+				if token_class_id == class_id && kitty_id >= start {
+					Some(kitty_id)
+				} else {
+					None
+				}
+			})
+			.collect::<Vec<_>>();
+		kitty_ids.sort();
+		kitty_ids.truncate(count as usize);
+		kitty_ids
+	}
+
+	/// Details of a single kitty.
+	pub fn kitty_info(kitty_id: KittyIndexOf<T>) -> Option<KittyInfo<T::AccountId, BalanceOf<T>>> {
+		orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id).map(|token| KittyInfo {
+			owner: token.owner,
+			gender: token.data.dna.gender(),
+			phenotype: token.data.dna.phenotype(),
+			dna: token.data.dna,
+			price: Self::current_price(kitty_id),
+			metadata: KittyMetadata::decode(&mut &token.metadata[..]).ok(),
+		})
+	}
+
+	/// The price a kitty can be bought for at the current block. None means not for sale.
+	pub fn current_price(kitty_id: KittyIndexOf<T>) -> Option<BalanceOf<T>> {
+		Self::kitty_prices(kitty_id).or_else(|| {
+			Self::dutch_listings(kitty_id).map(|listing| listing.price_at(frame_system::Pallet::<T>::block_number()))
+		})
+	}
+
+	/// The price of creating a kitty.
+	pub fn mint_price() -> BalanceOf<T> {
+		let minted: BalanceOf<T> = Self::gen0_minted().into();
+		T::Gen0BasePrice::get().saturating_add(T::Gen0PriceIncrement::get().saturating_mul(minted))
+	}
+
+	/// The number of kitties that can still be created.
+	pub fn remaining_supply() -> u32 {
+		T::MaxGen0Supply::get().saturating_sub(Self::gen0_minted())
+	}
+
+	/// Kitties for sale and their prices, in ascending order of id, starting from `start`.
+	pub fn listed_kitties(start: KittyIndexOf<T>, count: u32) -> Vec<(KittyIndexOf<T>, BalanceOf<T>)> {
+		let start_key = Self::listing_key(start);
+		// `iter_from` starts after the given key
+		ListedKitties::<T>::get(start_key)
+			.into_iter()
+			.chain(ListedKitties::<T>::iter_from(ListedKitties::<T>::hashed_key_for(start_key)).map(|(_, kitty_id)| kitty_id))
+			.filter_map(|kitty_id| Self::current_price(kitty_id).map(|price| (kitty_id, price)))
+			.take(count as usize)
+			.collect()
+	}
+
+	/// The key of a kitty in `ListedKitties`. Big endian so that keys sort in order of id.
+	pub(crate) fn listing_key(kitty_id: KittyIndexOf<T>) -> [u8; 16] {
+		let kitty_id: u128 = kitty_id.unique_saturated_into();
+		kitty_id.to_be_bytes()
+	}
+
+	/// Ensure a kitty is free to be transferred, priced or bred.
+	fn ensure_unlocked(kitty_id: KittyIndexOf<T>) -> DispatchResult {
+		ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+		ensure!(!Pregnancies::<T>::contains_key(kitty_id), Error::<T>::KittyPregnant);
+		Ok(())
+	}
+
+	fn transfer_kitty(sender: &T::AccountId, to: &T::AccountId, kitty_id: KittyIndexOf<T>) -> DispatchResult {
+		Self::ensure_unlocked(kitty_id)?;
+
+		Self::do_transfer(sender, to, kitty_id)?;
+
+		if sender != to {
+			Self::deposit_event(Event::KittyTransferred(sender.clone(), to.clone(), kitty_id));
+		}
+
+		Ok(())
+	}
+
+	fn set_kitty_price(sender: &T::AccountId, kitty_id: KittyIndexOf<T>, new_price: Option<BalanceOf<T>>) -> DispatchResult {
+		let (owner, _) = Self::approved_kitty(sender, kitty_id).ok_or(Error::<T>::NotOwner)?;
+		Self::ensure_unlocked(kitty_id)?;
+
+		KittyPrices::<T>::mutate_exists(kitty_id, |price| *price = new_price);
+		DutchListings::<T>::remove(kitty_id);
+		if new_price.is_some() {
+			ListedKitties::<T>::insert(Self::listing_key(kitty_id), kitty_id);
+		} else {
+			ListedKitties::<T>::remove(Self::listing_key(kitty_id));
+		}
+
+		Self::deposit_event(Event::KittyPriceUpdated(owner, kitty_id, new_price));
+
+		Ok(())
+	}
+
+	/// Move a kitty to a new owner and clear the state tied to the old owner.
+	/// The kitty deposit moves from the old owner to the new owner. The metadata is cleared and
+	/// its deposit released, as it is paid by the old owner.
+	#[transactional]
+	fn do_transfer(from: &T::AccountId, to: &T::AccountId, kitty_id: KittyIndexOf<T>) -> DispatchResult {
+		orml_nft::Pallet::<T>::transfer(from, to, (Self::class_id(), kitty_id))?;
+
+		if from != to {
+			Self::clear_listings(kitty_id);
+			Self::clear_approval(from, kitty_id);
+			Self::clear_metadata(from, kitty_id);
+			Self::release_kitty(from, kitty_id);
+			let deposit = Self::hold_kitty(to)?;
+			KittyDeposits::<T>::insert(kitty_id, (to.clone(), deposit));
+		}
+
+		Ok(())
+	}
+
+	/// Remove the sale, siring and auto breeding listings and the offers of a kitty.
+	fn clear_listings(kitty_id: KittyIndexOf<T>) {
+		KittyPrices::<T>::remove(kitty_id);
+		DutchListings::<T>::remove(kitty_id);
+		ListedKitties::<T>::remove(Self::listing_key(kitty_id));
+		SiringFees::<T>::remove(kitty_id);
+		Self::disable_auto_breed(kitty_id);
+
+		for (bidder, offer) in Offers::<T>::drain_prefix(kitty_id) {
+			T::Currency::unreserve(&bidder, offer.amount.saturating_add(offer.deposit));
+			Self::remove_offer_expiry(offer.expiry, kitty_id, &bidder);
+		}
+	}
+
+	/// Remove an offer from the expiry index.
+	fn remove_offer_expiry(expiry: T::BlockNumber, kitty_id: KittyIndexOf<T>, bidder: &T::AccountId) {
+		OfferExpiryAt::<T>::mutate_exists(expiry, |expiries| {
+			if let Some(offers) = expiries {
+				offers.retain(|(id, who)| *id != kitty_id || who != bidder);
+				if offers.is_empty() {
+					*expiries = None;
+				}
+			}
+		});
+	}
+
+	/// Remove the approved account of a kitty.
+	fn clear_approval(owner: &T::AccountId, kitty_id: KittyIndexOf<T>) {
+		if Approvals::<T>::take(kitty_id).is_some() {
+			Self::deposit_event(Event::KittyApproved(owner.clone(), kitty_id, None));
+		}
+	}
+
+	/// Remove the metadata of a kitty and unreserve its deposit.
+	fn clear_metadata(owner: &T::AccountId, kitty_id: KittyIndexOf<T>) {
+		if MetadataDeposits::<T>::contains_key(kitty_id) {
+			Self::release_metadata_deposit(kitty_id);
+			orml_nft::Tokens::<T>::mutate(Self::class_id(), kitty_id, |token| {
+				if let Some(token) = token {
+					token.metadata = Default::default();
+				}
+			});
+			Self::deposit_event(Event::KittyMetadataCleared(owner.clone(), kitty_id));
+		}
+	}
+
+	/// Unreserve the metadata deposit of a kitty from the account that paid it.
+	fn release_metadata_deposit(kitty_id: KittyIndexOf<T>) {
+		if let Some((depositor, deposit)) = MetadataDeposits::<T>::take(kitty_id) {
+			T::Currency::unreserve(&depositor, deposit);
+		}
+	}
+
+	/// Count a kitty for `who` and reserve the kitty deposit.
+	fn hold_kitty(who: &T::AccountId) -> Result<BalanceOf<T>, DispatchError> {
+		let count = Self::kitty_count(who);
+		ensure!(count < T::MaxKittiesPerAccount::get(), Error::<T>::TooManyKitties);
+
+		let deposit = T::KittyDeposit::get();
+		T::Currency::reserve(who, deposit)?;
+		KittyCount::<T>::insert(who, count + 1);
+
+		Ok(deposit)
+	}
+
+	/// Whether `hold_kitty` would succeed for `who`.
+	fn can_hold_kitty(who: &T::AccountId) -> bool {
+		Self::kitty_count(who) < T::MaxKittiesPerAccount::get() &&
+			T::Currency::can_reserve(who, T::KittyDeposit::get())
+	}
+
+	/// Reverse `hold_kitty`.
+	fn unhold_kitty(who: &T::AccountId, deposit: BalanceOf<T>) {
+		T::Currency::unreserve(who, deposit);
+		KittyCount::<T>::mutate_exists(who, |count| {
+			*count = count.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+		});
+	}
+
+	/// Uncount a kitty held by `who` and release its deposit. Genesis kitties hold no deposit.
+	fn release_kitty(who: &T::AccountId, kitty_id: KittyIndexOf<T>) {
+		let deposit = KittyDeposits::<T>::take(kitty_id).map_or_else(Zero::zero, |(_, deposit)| deposit);
+		Self::unhold_kitty(who, deposit);
+	}
+
+	/// The account paying burn refunds.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	fn settle_auction(kitty_id: KittyIndexOf<T>, auction: AuctionOf<T>) {
+		match auction.bid {
+			Some((bidder, amount, deposit)) => {
+				if Self::do_settle_auction(&auction.seller, &bidder, kitty_id, amount, deposit).is_ok() {
+					Self::deposit_event(Event::AuctionSold(auction.seller, bidder, kitty_id, amount));
+				} else {
+					// the payment could not be made, refund the bidder
+					T::Currency::unreserve(&bidder, amount.saturating_add(deposit));
+					Self::deposit_event(Event::AuctionExpired(auction.seller, kitty_id));
+				}
+			},
+			None => {
+				Self::deposit_event(Event::AuctionExpired(auction.seller, kitty_id));
+			},
+		}
+	}
+
+	#[transactional]
+	fn do_settle_auction(
+		seller: &T::AccountId,
+		bidder: &T::AccountId,
+		kitty_id: KittyIndexOf<T>,
+		amount: BalanceOf<T>,
+		deposit: BalanceOf<T>,
+	) -> DispatchResult {
+		// the deposit is reserved again by the transfer
+		T::Currency::unreserve(bidder, amount.saturating_add(deposit));
+		Self::do_transfer(seller, bidder, kitty_id)?;
+		Self::pay_for_kitty(bidder, seller, kitty_id, amount, ExistenceRequirement::AllowDeath)
+	}
+
+	/// Ensure a kitty is off cooldown and below the lifetime breeding cap.
+	fn ensure_can_breed(kitty_id: KittyIndexOf<T>) -> DispatchResult {
+		let state = Self::breeding_states(kitty_id);
+
+		ensure!(!Pregnancies::<T>::contains_key(kitty_id), Error::<T>::KittyPregnant);
+		ensure!(T::MaxBreedCount::get().map_or(true, |max| state.breed_count < max), Error::<T>::BreedLimitReached);
+		ensure!(frame_system::Pallet::<T>::block_number() >= state.cooldown_end, Error::<T>::KittyOnCooldown);
+
+		Ok(())
+	}
+
+	/// Count a breeding and start the cooldown for it.
+	fn record_breeding(kitty_id: KittyIndexOf<T>) {
+		let cooldowns = T::BreedingCooldowns::get();
+
+		BreedingStates::<T>::mutate(kitty_id, |state| {
+			let cooldown = cooldowns.get(state.breed_count as usize)
+				.or_else(|| cooldowns.last())
+				.copied()
+				.unwrap_or_else(Zero::zero);
+
+			state.breed_count = state.breed_count.saturating_add(1);
+			state.cooldown_end = frame_system::Pallet::<T>::block_number().saturating_add(cooldown);
+		});
+	}
+
+	/// The generation of a kitty. Kitties without pedigree are generation 0.
+	pub fn generation(kitty_id: KittyIndexOf<T>) -> u32 {
+		Self::pedigrees(kitty_id).map_or(0, |pedigree| pedigree.generation)
+	}
+
+	/// Ancestors of a kitty up to `depth` generations back, capped by `MAX_ANCESTRY_DEPTH`.
+	/// Each ancestor is returned once with the depth it is first found at, nearest first.
+	pub fn ancestors(kitty_id: KittyIndexOf<T>, depth: u32) -> Vec<(KittyIndexOf<T>, u32)> {
+		let mut ancestors: Vec<(KittyIndexOf<T>, u32)> = Vec::new();
+		let mut current = vec![kitty_id];
+
+		for level in 1..=depth.min(MAX_ANCESTRY_DEPTH) {
+			let mut next = Vec::new();
+
+			for id in current {
+				if let Some((parent1, parent2)) = Self::pedigrees(id).and_then(|pedigree| pedigree.parents) {
+					for &parent in &[parent1, parent2] {
+						if !ancestors.iter().any(|(ancestor, _)| *ancestor == parent) {
+							ancestors.push((parent, level));
+							next.push(parent);
+						}
+					}
+				}
+			}
+
+			if next.is_empty() {
+				break;
+			}
+			current = next;
+		}
+
+		ancestors
+	}
+
+	/// The royalty of a kitty.
+	pub fn royalty(kitty_id: KittyIndexOf<T>) -> Perbill {
+		Self::kitty_royalties(kitty_id).unwrap_or_else(T::DefaultRoyalty::get)
+	}
+
+	/// Pay for a kitty sale, splitting the royalty to the creator on secondary sales.
+	fn pay_for_kitty(
+		buyer: &T::AccountId,
+		seller: &T::AccountId,
+		kitty_id: KittyIndexOf<T>,
+		price: BalanceOf<T>,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		let mut proceeds = price;
+
+		if let Some(creator) = Self::kitty_creators(kitty_id) {
+			let royalty = Self::royalty(kitty_id).mul_floor(price);
+			// a royalty too small to keep the account of the creator alive stays with the seller
+			if creator != *seller && !royalty.is_zero() &&
+				T::Currency::total_balance(&creator).saturating_add(royalty) >= T::Currency::minimum_balance()
+			{
+				T::Currency::transfer(buyer, &creator, royalty, existence_requirement)?;
+				proceeds = price.saturating_sub(royalty);
+
+				Self::deposit_event(Event::RoyaltyPaid(creator, kitty_id, royalty));
+			}
+		}
+
+		T::Currency::transfer(buyer, seller, proceeds, existence_requirement)
+	}
+
 	fn random_value(sender: &T::AccountId) -> [u8; 16] {
 		let payload = (
 			T::Randomness::random_seed().0,
@@ -287,37 +1821,253 @@ impl<T: Config> Pallet<T> {
 		payload.using_encoded(blake2_128)
 	}
 
+	/// The probability of a gene of a kitten to mutate.
+	pub fn mutation_rate() -> Perbill {
+		MutationRate::<T>::get().unwrap_or_else(T::DefaultMutationRate::get)
+	}
+
+	/// Flip a random bit of each gene with probability `mutation_rate`, using randomness derived
+	/// from the breeding seed. Returns the indexes of the mutated genes.
+	fn mutate(kitty: &mut Kitty, seed: [u8; 16]) -> Vec<u8> {
+		let mutation_rate = Self::mutation_rate();
+		if mutation_rate.is_zero() {
+			return Vec::new();
+		}
+
+		let mut rng = ChaChaRng::from_seed((seed, b"kitties/mutation").using_encoded(blake2_256));
+		let threshold = mutation_rate.mul_floor(u32::max_value());
+
+		let mut mutated = Vec::new();
+		for (i, gene) in kitty.0.iter_mut().enumerate() {
+			let roll = rng.next_u32();
+			let bit = rng.next_u32() % 8;
+			if roll < threshold {
+				*gene ^= 1 << bit;
+				mutated.push(i as u8);
+			}
+		}
+		mutated
+	}
+
+	/// Make the female of a pair pregnant. The kitten is born after `GestationPeriod` blocks.
+	/// `parent_owners` are the owners of `kitty1` and `kitty2`, the kitten belongs to `owner`.
 	fn do_breed(
 		owner: T::AccountId,
+		parent_owners: (T::AccountId, T::AccountId),
+		kitty_id_1: KittyIndexOf<T>,
 		kitty1: Kitty,
+		kitty_id_2: KittyIndexOf<T>,
 		kitty2: Kitty,
+		source: KittySource,
 	) -> DispatchResult {
-		ensure!(kitty1.gender() != kitty2.gender(), Error::<T>::SameGender);
+		// the seed is only known at birth, check the pair is compatible
+		T::Breeding::breed(&kitty1, &kitty2, Default::default()).map_err(Error::<T>::from)?;
 
-		let kitty1_dna = kitty1.0;
-		let kitty2_dna = kitty2.0;
+		Self::ensure_can_breed(kitty_id_1)?;
+		Self::ensure_can_breed(kitty_id_2)?;
 
-		let selector = Self::random_value(&owner);
-		let mut new_dna = [0u8; 16];
+		let (owner1, owner2) = parent_owners;
+		let ((mother_id, mother_owner), (father_id, father_owner)) = if kitty1.gender() == KittyGender::Female {
+			((kitty_id_1, owner1), (kitty_id_2, owner2))
+		} else {
+			((kitty_id_2, owner2), (kitty_id_1, owner1))
+		};
+		let due = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::GestationPeriod::get().max(One::one()));
+
+		let deposit = Self::hold_kitty(&owner)?;
+
+		Self::record_breeding(kitty_id_1);
+		Self::record_breeding(kitty_id_2);
+		Pregnancies::<T>::insert(mother_id, Pregnancy {
+			owner: owner.clone(),
+			mother_owner: mother_owner.clone(),
+			father_owner: father_owner.clone(),
+			deposit,
+			parents: (kitty_id_1, kitty_id_2),
+			dna: (kitty1, kitty2),
+			source,
+			due,
+		});
+		BirthsAt::<T>::insert(due, mother_id, ());
+
+		Self::deposit_event(Event::Pregnant(owner, mother_id, mother_owner, father_id, father_owner, due));
 
-		// Combine parents and selector to create new kitty
-		for i in 0..kitty1_dna.len() {
-			new_dna[i] = combine_dna(kitty1_dna[i], kitty2_dna[i], selector[i]);
-		}
+		Ok(())
+	}
+
+	/// Seed of the kitten of a pregnancy, from the randomness of the block it is born at.
+	fn birth_seed(mother_id: KittyIndexOf<T>, owner: &T::AccountId) -> [u8; 16] {
+		let payload = (
+			T::Randomness::random(&(b"kitties/birth", mother_id).encode()).0,
+			owner,
+			<frame_system::Pallet<T>>::extrinsic_index(),
+		);
+		payload.using_encoded(blake2_128)
+	}
 
-		let new_kitty = Kitty(new_dna);
-		let kitty_id = orml_nft::Pallet::<T>::mint(&owner, Self::class_id(), Vec::new(), new_kitty.clone())?;
+	fn give_birth(mother_id: KittyIndexOf<T>, pregnancy: PregnancyOf<T>) {
+		let owner = pregnancy.owner.clone();
+		let deposit = pregnancy.deposit;
+		if Self::do_give_birth(mother_id, pregnancy).is_err() {
+			Self::unhold_kitty(&owner, deposit);
+			Self::deposit_event(Event::BirthFailed(owner, mother_id));
+		}
+	}
 
-		Self::deposit_event(Event::KittyBred(owner, kitty_id, new_kitty));
+	#[transactional]
+	fn do_give_birth(mother_id: KittyIndexOf<T>, pregnancy: PregnancyOf<T>) -> DispatchResult {
+		let Pregnancy {
+			owner,
+			mother_owner,
+			father_owner,
+			deposit,
+			parents: (kitty_id_1, kitty_id_2),
+			dna: (kitty1, kitty2),
+			source,
+			..
+		} = pregnancy;
+
+		let seed = Self::birth_seed(mother_id, &owner);
+		let mut new_kitty = T::Breeding::breed(&kitty1, &kitty2, seed).map_err(Error::<T>::from)?;
+		let mutated = Self::mutate(&mut new_kitty, seed);
+
+		let kitty_id = orml_nft::Pallet::<T>::mint(&owner, Self::class_id(), Vec::new(), KittyRecord {
+			dna: new_kitty.clone(),
+			source,
+		})?;
+		KittyDeposits::<T>::insert(kitty_id, (owner.clone(), deposit));
+		KittyCreators::<T>::insert(kitty_id, &owner);
+		Pedigrees::<T>::insert(kitty_id, Pedigree {
+			parents: Some((kitty_id_1, kitty_id_2)),
+			generation: Self::generation(kitty_id_1).max(Self::generation(kitty_id_2)).saturating_add(1),
+			birth: frame_system::Pallet::<T>::block_number(),
+		});
+
+		Self::deposit_event(Event::KittyBred(owner, kitty_id, new_kitty, mother_owner, father_owner));
+		if !mutated.is_empty() {
+			Self::deposit_event(Event::KittyMutated(kitty_id, mutated));
+		}
 
 		Ok(())
 	}
 
+	/// A pair of kitties in ascending order of id.
+	fn auto_breed_pair(kitty_id_1: KittyIndexOf<T>, kitty_id_2: KittyIndexOf<T>) -> (KittyIndexOf<T>, KittyIndexOf<T>) {
+		if kitty_id_1 <= kitty_id_2 {
+			(kitty_id_1, kitty_id_2)
+		} else {
+			(kitty_id_2, kitty_id_1)
+		}
+	}
+
+	/// Ensure a pair of kitties exists, is opted in to auto breeding and can breed now.
+	/// Returns the owners and dna of the kitties.
+	fn ensure_auto_breedable(
+		kitty_id_1: KittyIndexOf<T>,
+		kitty_id_2: KittyIndexOf<T>,
+	) -> Result<((T::AccountId, Kitty), (T::AccountId, Kitty)), DispatchError> {
+		let kitty1 = orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
+		let kitty2 = orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+
+		ensure!(Self::is_auto_breed_enabled(kitty_id_1) && Self::is_auto_breed_enabled(kitty_id_2), Error::<T>::AutoBreedNotEnabled);
+		Self::ensure_unlocked(kitty_id_1)?;
+		Self::ensure_unlocked(kitty_id_2)?;
+
+		T::Breeding::breed(&kitty1.data.dna, &kitty2.data.dna, Default::default()).map_err(Error::<T>::from)?;
+		Self::ensure_can_breed(kitty_id_1)?;
+		Self::ensure_can_breed(kitty_id_2)?;
+
+		Ok(((kitty1.owner, kitty1.data.dna), (kitty2.owner, kitty2.data.dna)))
+	}
+
+	/// The owner of the kitten of an auto breed of a pair of kitties with `nonce`.
+	///
+	/// The owners of the pair take turns by the parity of the nonce of the pair. The owner with
+	/// the first turn is decided by the parity of the hash of the pair, so neither the lower nor
+	/// the higher id is favoured. The order the kitties are submitted in does not matter.
+	fn auto_breed_owner(
+		(kitty_id_1, owner1): (KittyIndexOf<T>, &T::AccountId),
+		(kitty_id_2, owner2): (KittyIndexOf<T>, &T::AccountId),
+		nonce: u32,
+	) -> T::AccountId {
+		let lower_first = Self::auto_breed_pair(kitty_id_1, kitty_id_2).using_encoded(blake2_128)[0] % 2 == 0;
+		let lower_turn = (nonce % 2 == 0) == lower_first;
+		if lower_turn == (kitty_id_1 <= kitty_id_2) {
+			owner1.clone()
+		} else {
+			owner2.clone()
+		}
+	}
+
+	/// The nonce an auto breed solution for a pair of kitties must use.
+	pub fn auto_breed_nonce(kitty_id_1: KittyIndexOf<T>, kitty_id_2: KittyIndexOf<T>) -> u32 {
+		AutoBreedNonces::<T>::get(Self::auto_breed_pair(kitty_id_1, kitty_id_2))
+	}
+
+	/// Whether a kitty is opted in to auto breeding.
+	pub fn is_auto_breed_enabled(kitty_id: KittyIndexOf<T>) -> bool {
+		AutoBreedPositions::<T>::contains_key(kitty_id)
+	}
+
+	/// Add a kitty to the end of the auto breed index.
+	fn enable_auto_breed(kitty_id: KittyIndexOf<T>) {
+		if Self::is_auto_breed_enabled(kitty_id) {
+			return;
+		}
+
+		let count = Self::auto_breed_kitty_count();
+		AutoBreedKitties::<T>::insert(count, kitty_id);
+		AutoBreedPositions::<T>::insert(kitty_id, count);
+		AutoBreedKittyCount::<T>::put(count.saturating_add(1));
+	}
+
+	/// Remove a kitty from the auto breed index, moving the last kitty into its position.
+	fn disable_auto_breed(kitty_id: KittyIndexOf<T>) {
+		let position = match AutoBreedPositions::<T>::take(kitty_id) {
+			Some(position) => position,
+			None => return,
+		};
+
+		let last = Self::auto_breed_kitty_count().saturating_sub(1);
+		if let Some(last_kitty_id) = AutoBreedKitties::<T>::take(last) {
+			if position != last {
+				AutoBreedKitties::<T>::insert(position, last_kitty_id);
+				AutoBreedPositions::<T>::insert(last_kitty_id, position);
+			}
+		}
+		AutoBreedKittyCount::<T>::put(last);
+	}
+
+	/// The auto breed difficulty.
+	pub fn difficulty() -> u32 {
+		Difficulty::<T>::get().unwrap_or_else(T::DefaultDifficulty::get)
+	}
+
+	/// Scale the difficulty by the number of auto breeds in the ending period relative to
+	/// `TargetAutoBreeds`, by at most `MAX_DIFFICULTY_ADJUSTMENT` times either way.
+	fn adjust_difficulty() {
+		let count = AutoBreedCount::<T>::take();
+		let target = T::TargetAutoBreeds::get().max(1);
+		let old = Self::difficulty();
+
+		let scaled = (old as u128).saturating_mul(count as u128) / target as u128;
+		let new = (scaled.min(u32::max_value() as u128) as u32)
+			.max(old / MAX_DIFFICULTY_ADJUSTMENT)
+			.min(old.saturating_mul(MAX_DIFFICULTY_ADJUSTMENT))
+			.max(1);
+
+		if new != old {
+			Difficulty::<T>::put(new);
+			Self::deposit_event(Event::DifficultyUpdated(new));
+		}
+	}
+
 	fn validate_solution(kitty_id_1: KittyIndexOf<T>, kitty_id_2: KittyIndexOf<T>, nonce: u32, solution: u128) -> bool {
 		let payload = (kitty_id_1, kitty_id_2, nonce, solution);
 		let hash = payload.using_encoded(blake2_128);
 		let hash_value = u128::from_le_bytes(hash);
-		let difficulty = T::DefaultDifficulty::get();
+		let difficulty = Self::difficulty();
 
 		hash_value < (u128::max_value() / difficulty as u128)
 	}
@@ -329,28 +2079,34 @@ impl<T: Config> Pallet<T> {
 		let random_seed = sp_io::offchain::random_seed();
 		let mut rng = ChaChaRng::from_seed(random_seed);
 
-		// this only support if kitty_count <= u32::max_value()
-		let kitty_count = TryInto::<u32>::try_into(orml_nft::Pallet::<T>::next_token_id(Self::class_id())).map_err(|_| ())?;
+		// only kitties opted in to auto breeding are sampled
+		let kitty_count = Self::auto_breed_kitty_count();
 
-		if kitty_count == 0 {
+		if kitty_count < 2 {
 			return Ok(());
 		}
 
 		const MAX_ITERATIONS: u128 = 500;
 
-		let nonce = Self::auto_breed_nonce();
-
 		let mut remaining_iterations = MAX_ITERATIONS;
 
 		let (kitty_1, kitty_2) = loop {
-			let kitty_id_1: KittyIndexOf<T> = (rng.next_u32() % kitty_count).into();
-			let kitty_id_2: KittyIndexOf<T> = (rng.next_u32() % kitty_count).into();
-
-			let kitty_1 = orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id_1).ok_or(())?;
-			let kitty_2 = orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id_2).ok_or(())?;
-
-			if kitty_1.data.gender() != kitty_2.data.gender() {
-				break (kitty_id_1, kitty_id_2);
+			let kitty_ids = AutoBreedKitties::<T>::get(rng.next_u32() % kitty_count)
+				.zip(AutoBreedKitties::<T>::get(rng.next_u32() % kitty_count));
+
+			let tokens = kitty_ids.and_then(|(kitty_id_1, kitty_id_2)| {
+				orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id_1)
+					.zip(orml_nft::Pallet::<T>::tokens(Self::class_id(), kitty_id_2))
+					.map(|tokens| ((kitty_id_1, kitty_id_2), tokens))
+			});
+
+			if let Some(((kitty_id_1, kitty_id_2), (kitty_1, kitty_2))) = tokens {
+				if T::Breeding::breed(&kitty_1.data.dna, &kitty_2.data.dna, Default::default()).is_ok() &&
+					Self::ensure_can_breed(kitty_id_1).is_ok() &&
+					Self::ensure_can_breed(kitty_id_2).is_ok()
+				{
+					break (kitty_id_1, kitty_id_2);
+				}
 			}
 
 			remaining_iterations -= 1;
@@ -360,6 +2116,7 @@ impl<T: Config> Pallet<T> {
 			}
 		};
 
+		let nonce = Self::auto_breed_nonce(kitty_1, kitty_2);
 		let solution_prefix = rng.next_u32() as u128;
 
 		for i in 0 .. remaining_iterations {
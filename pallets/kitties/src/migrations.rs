@@ -0,0 +1,129 @@
+use super::*;
+use frame_support::traits::PalletInfoAccess;
+use sp_io::hashing::twox_128;
+
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+/// orml_nft token info before `Releases::V2`, when the token data is the bare kitty dna.
+type OldTokenInfoOf<T> = orml_nft::TokenInfo<
+	<T as frame_system::Config>::AccountId,
+	Kitty,
+	orml_nft::TokenMetadataOf<T>,
+>;
+
+/// Translate every kitty token into a `KittyRecord` and count the kitties held by each account.
+///
+/// Chains at `Releases::V1` predate pedigrees and do not record how a kitty came into existence,
+/// so every existing kitty is recorded as `Created` and counts toward `MaxGen0Supply`. Existing
+/// kitties hold no deposit. Kitties with a fixed price are added to `ListedKitties`. The global
+/// `AutoBreedNonce` replaced by `AutoBreedNonces` is removed.
+///
+/// The orml_nft instance is only used by this pallet, so every token is a kitty.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+	if StorageVersion::<T>::get() != Releases::V1 {
+		return T::DbWeight::get().reads(1);
+	}
+
+	let mut translated = 0u64;
+
+	orml_nft::Tokens::<T>::translate::<OldTokenInfoOf<T>, _>(|_class_id, _kitty_id, token| {
+		translated += 1;
+
+		KittyCount::<T>::mutate(&token.owner, |count| *count = count.saturating_add(1));
+
+		Some(orml_nft::TokenInfo {
+			metadata: token.metadata,
+			owner: token.owner,
+			data: KittyRecord {
+				dna: token.data,
+				source: KittySource::Created,
+			},
+		})
+	});
+
+	let minted: u32 = translated.unique_saturated_into();
+	Gen0Minted::<T>::put(minted);
+
+	let mut listed = 0u64;
+
+	for (kitty_id, _) in KittyPrices::<T>::iter() {
+		listed += 1;
+		ListedKitties::<T>::insert(Pallet::<T>::listing_key(kitty_id), kitty_id);
+	}
+
+	frame_support::storage::unhashed::kill(&old_auto_breed_nonce_key::<T>());
+
+	StorageVersion::<T>::put(Releases::V2);
+
+	T::DbWeight::get().reads_writes(
+		translated.saturating_mul(2).saturating_add(listed).saturating_add(1),
+		translated.saturating_mul(2).saturating_add(listed).saturating_add(3),
+	)
+}
+
+/// Storage key of the global `AutoBreedNonce` value used before `Releases::V2`.
+pub(crate) fn old_auto_breed_nonce_key<T: Config>() -> Vec<u8> {
+	let mut key = twox_128(<Pallet<T> as PalletInfoAccess>::name().as_bytes()).to_vec();
+	key.extend_from_slice(&twox_128(b"AutoBreedNonce"));
+	key
+}
+
+/// Owners of all kitties according to the owner index, which is not touched by the migration.
+#[cfg(feature = "try-runtime")]
+fn indexed_owners<T: Config>() -> Vec<(KittyIndexOf<T>, T::AccountId)> {
+	let class_id = Pallet::<T>::class_id();
+	let mut owners = orml_nft::TokensByOwner::<T>::iter()
+		.filter_map(|(owner, (token_class_id, kitty_id), _)| {
+			if token_class_id == class_id {
+				Some((kitty_id, owner))
+			} else {
+				None
+			}
+		})
+		.collect::<Vec<_>>();
+	owners.sort();
+	owners
+}
+
+#[cfg(feature = "try-runtime")]
+pub fn pre_migrate_to_v2<T: Config>() -> Result<(), &'static str> {
+	if StorageVersion::<T>::get() != Releases::V1 {
+		return Ok(());
+	}
+
+	Pallet::<T>::set_temp_storage(indexed_owners::<T>(), "kitty_owners");
+
+	Ok(())
+}
+
+#[cfg(feature = "try-runtime")]
+pub fn post_migrate_to_v2<T: Config>() -> Result<(), &'static str> {
+	ensure!(StorageVersion::<T>::get() == Releases::V2, "storage version not updated");
+	ensure!(
+		!frame_support::storage::unhashed::exists(&old_auto_breed_nonce_key::<T>()),
+		"old auto breed nonce not removed"
+	);
+
+	// decoding every token also checks the new format
+	let mut owners = orml_nft::Tokens::<T>::iter_prefix(Pallet::<T>::class_id())
+		.map(|(kitty_id, token)| (kitty_id, token.owner))
+		.collect::<Vec<_>>();
+	owners.sort();
+
+	let indexed = indexed_owners::<T>();
+	ensure!(owners == indexed, "kitty owners do not match the owner index");
+
+	for (_, owner) in owners.iter() {
+		let held = owners.iter().filter(|(_, other)| other == owner).count() as u32;
+		ensure!(Pallet::<T>::kitty_count(owner) >= held, "kitty count not updated");
+	}
+
+	if let Some(previous) = Pallet::<T>::get_temp_storage::<Vec<(KittyIndexOf<T>, T::AccountId)>>("kitty_owners") {
+		ensure!(owners.len() == previous.len(), "kitty count changed");
+		ensure!(owners == previous, "kitty owners changed");
+		ensure!(Pallet::<T>::gen0_minted() as usize == previous.len(), "gen0 minted not updated");
+	}
+
+	Ok(())
+}
@@ -0,0 +1,185 @@
+//! Visible traits of a kitty decoded from its dna.
+//!
+//! Genome layout, one byte per gene:
+//!
+//! | byte  | gene                               |
+//! |-------|------------------------------------|
+//! | 0     | gender, even is male               |
+//! | 1     | fur colour                         |
+//! | 2     | fur pattern                        |
+//! | 3     | eye colour                         |
+//! | 4     | eye shape                          |
+//! | 5     | body type                          |
+//! | 6..8  | unused                             |
+//! | 8..16 | recessive alleles of bytes 0 to 7  |
+//!
+//! Trait genes map to one of 8 variants by their top 3 bits. The last variant of each trait is
+//! rare and the number of rare traits determines the rarity tier.
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::KittyGender;
+
+pub const GENDER_GENE: usize = 0;
+pub const FUR_COLOUR_GENE: usize = 1;
+pub const FUR_PATTERN_GENE: usize = 2;
+pub const EYE_COLOUR_GENE: usize = 3;
+pub const EYE_SHAPE_GENE: usize = 4;
+pub const BODY_TYPE_GENE: usize = 5;
+/// Offset of the recessive allele of a gene.
+pub const RECESSIVE_OFFSET: usize = 8;
+/// The genes with a visible trait.
+pub const TRAIT_GENES: [usize; 5] = [FUR_COLOUR_GENE, FUR_PATTERN_GENE, EYE_COLOUR_GENE, EYE_SHAPE_GENE, BODY_TYPE_GENE];
+
+macro_rules! gene_trait {
+	(
+		$( #[$attr:meta] )*
+		pub enum $name:ident { $( $variant:ident, )* }
+	) => {
+		$( #[$attr] )*
+		#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+		#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+		pub enum $name { $( $variant, )* }
+
+		impl $name {
+			const VARIANTS: [$name; 8] = [ $( $name::$variant, )* ];
+
+			pub fn from_gene(gene: u8) -> Self {
+				Self::VARIANTS[(gene >> 5) as usize]
+			}
+		}
+	};
+}
+
+gene_trait! {
+	pub enum FurColour {
+		Ginger,
+		Black,
+		White,
+		Grey,
+		Cream,
+		Chocolate,
+		Cinnamon,
+		Lilac,
+	}
+}
+
+gene_trait! {
+	pub enum FurPattern {
+		Solid,
+		Tabby,
+		Bicolour,
+		Tuxedo,
+		Tortoiseshell,
+		Calico,
+		Pointed,
+		Spotted,
+	}
+}
+
+gene_trait! {
+	pub enum EyeColour {
+		Green,
+		Hazel,
+		Amber,
+		Copper,
+		Blue,
+		Gold,
+		Grey,
+		/// Eyes of different colours.
+		Odd,
+	}
+}
+
+gene_trait! {
+	pub enum EyeShape {
+		Round,
+		Almond,
+		Oval,
+		Slanted,
+		Sleepy,
+		Wide,
+		Winking,
+		Starry,
+	}
+}
+
+gene_trait! {
+	pub enum BodyType {
+		Cobby,
+		SemiCobby,
+		Moderate,
+		Foreign,
+		Oriental,
+		Lean,
+		Substantial,
+		Chonky,
+	}
+}
+
+/// Rarity tier by the number of rare traits.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rarity {
+	Common,
+	Uncommon,
+	Rare,
+	Epic,
+	/// Four or more rare traits.
+	Legendary,
+}
+
+/// The visible traits of a kitty.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Phenotype {
+	pub gender: KittyGender,
+	pub fur_colour: FurColour,
+	pub fur_pattern: FurPattern,
+	pub eye_colour: EyeColour,
+	pub eye_shape: EyeShape,
+	pub body_type: BodyType,
+	pub rarity: Rarity,
+}
+
+impl Phenotype {
+	pub fn decode_dna(dna: &[u8; 16]) -> Self {
+		Phenotype {
+			gender: gender(dna),
+			fur_colour: FurColour::from_gene(dna[FUR_COLOUR_GENE]),
+			fur_pattern: FurPattern::from_gene(dna[FUR_PATTERN_GENE]),
+			eye_colour: EyeColour::from_gene(dna[EYE_COLOUR_GENE]),
+			eye_shape: EyeShape::from_gene(dna[EYE_SHAPE_GENE]),
+			body_type: BodyType::from_gene(dna[BODY_TYPE_GENE]),
+			rarity: rarity(dna),
+		}
+	}
+}
+
+pub fn gender(dna: &[u8; 16]) -> KittyGender {
+	if dna[GENDER_GENE] % 2 == 0 {
+		KittyGender::Male
+	} else {
+		KittyGender::Female
+	}
+}
+
+/// Whether a trait gene maps to the rare variant.
+pub fn is_rare(gene: u8) -> bool {
+	gene >> 5 == 7
+}
+
+pub fn rarity(dna: &[u8; 16]) -> Rarity {
+	let rare_traits = TRAIT_GENES.iter().filter(|&&i| is_rare(dna[i])).count();
+	match rare_traits {
+		0 => Rarity::Common,
+		1 => Rarity::Uncommon,
+		2 => Rarity::Rare,
+		3 => Rarity::Epic,
+		_ => Rarity::Legendary,
+	}
+}
@@ -22,7 +22,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		KittiesModule: kitties::{Pallet, Call, Storage, Event<T>, Config},
+		KittiesModule: kitties::{Pallet, Call, Storage, Event<T>, Config<T>},
 		Nft: orml_nft::{Pallet, Storage, Config<T>},
 	}
 );
@@ -59,7 +59,7 @@ impl frame_system::Config for Test {
 }
 
 parameter_types! {
-	pub const ExistentialDeposit: u64 = 1;
+	pub static ExistentialDeposit: u64 = 1;
 }
 impl pallet_balances::Config for Test {
 	type MaxLocks = ();
@@ -85,28 +85,82 @@ impl Randomness<H256, u64> for MockRandom {
 
 parameter_types! {
 	pub const MaxClassMetadata: u32 = 0;
-	pub const MaxTokenMetadata: u32 = 0;
+	pub const MaxTokenMetadata: u32 = 32;
 }
 
 impl orml_nft::Config for Test {
 	type ClassId = u32;
 	type TokenId = u32;
 	type ClassData = ();
-	type TokenData = Kitty;
+	type TokenData = KittyRecord;
 	type MaxClassMetadata = MaxClassMetadata;
 	type MaxTokenMetadata = MaxTokenMetadata;
 }
 
 parameter_types! {
 	pub const DefaultDifficulty: u32 = 3;
+	pub const DifficultyAdjustmentPeriod: u64 = 10;
+	pub const TargetAutoBreeds: u32 = 2;
+	pub const MaxOffersPerKitty: u32 = 2;
+	pub const MaxExpiriesPerBlock: u32 = 2;
+	pub const DefaultRoyalty: Perbill = Perbill::from_percent(10);
+	pub const MaxRoyalty: Perbill = Perbill::from_percent(50);
+	pub BreedingCooldowns: Vec<u64> = vec![5, 10];
+	pub const GestationPeriod: u64 = 2;
+	pub const MaxBreedCount: Option<u32> = Some(3);
+	pub const KittiesPalletId: PalletId = PalletId(*b"py/kitty");
+	pub const BurnRefund: u64 = 10;
+	pub const MaxNameLength: u32 = 8;
+	pub const MaxUriLength: u32 = 20;
+	pub const MetadataDepositBase: u64 = 10;
+	pub const MetadataDepositPerByte: u64 = 1;
+	pub const MaxBatchSize: u32 = 3;
+	pub const DefaultMutationRate: Perbill = Perbill::from_percent(0);
+	pub static MaxGen0Supply: u32 = 100;
+	pub static Gen0BasePrice: u64 = 0;
+	pub static Gen0PriceIncrement: u64 = 0;
+	pub static KittyDeposit: u64 = 0;
+	pub static MaxKittiesPerAccount: u32 = 10;
+}
+
+pub struct MintPayments;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for MintPayments {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Test>) {
+		Balances::resolve_creating(&999, amount);
+	}
 }
 
 impl Config for Test {
 	type Event = Event;
 	type Randomness = MockRandom;
+	type Breeding = BitmaskBreeding;
 	type Currency = Balances;
 	type WeightInfo = ();
 	type DefaultDifficulty = DefaultDifficulty;
+	type DifficultyAdjustmentPeriod = DifficultyAdjustmentPeriod;
+	type TargetAutoBreeds = TargetAutoBreeds;
+	type MaxOffersPerKitty = MaxOffersPerKitty;
+	type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+	type DefaultRoyalty = DefaultRoyalty;
+	type MaxRoyalty = MaxRoyalty;
+	type BreedingCooldowns = BreedingCooldowns;
+	type GestationPeriod = GestationPeriod;
+	type MaxBreedCount = MaxBreedCount;
+	type PalletId = KittiesPalletId;
+	type BurnRefund = BurnRefund;
+	type MaxNameLength = MaxNameLength;
+	type MaxUriLength = MaxUriLength;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type MaxGen0Supply = MaxGen0Supply;
+	type Gen0BasePrice = Gen0BasePrice;
+	type Gen0PriceIncrement = Gen0PriceIncrement;
+	type OnMintPayment = MintPayments;
+	type KittyDeposit = KittyDeposit;
+	type MaxKittiesPerAccount = MaxKittiesPerAccount;
+	type MaxBatchSize = MaxBatchSize;
+	type DefaultMutationRate = DefaultMutationRate;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
 }
 
 /// An extrinsic type used for tests.
@@ -125,17 +179,31 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
 	pallet_balances::GenesisConfig::<Test>{
-		balances: vec![(200, 500)],
+		balances: vec![(200, 500), (300, 500)],
 	}.assimilate_storage(&mut t).unwrap();
 
-	<crate::GenesisConfig as GenesisBuild<Test>>::assimilate_storage(&crate::GenesisConfig::default(), &mut t).unwrap();
+	<crate::GenesisConfig<Test> as GenesisBuild<Test>>::assimilate_storage(&crate::GenesisConfig::default(), &mut t).unwrap();
 
 	let mut t: sp_io::TestExternalities = t.into();
 
-	t.execute_with(|| System::set_block_number(1) );
+	t.execute_with(|| {
+		System::set_block_number(1);
+		MaxGen0Supply::set(100);
+		Gen0BasePrice::set(0);
+		Gen0PriceIncrement::set(0);
+		KittyDeposit::set(0);
+		MaxKittiesPerAccount::set(10);
+	});
 	t
 }
 
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		KittiesModule::on_initialize(System::block_number());
+	}
+}
+
 #[test]
 fn can_create() {
 	new_test_ext().execute_with(|| {
@@ -156,6 +224,60 @@ fn gender() {
 	assert_eq!(Kitty([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).gender(), KittyGender::Female);
 }
 
+#[test]
+fn phenotype() {
+	use crate::phenotype::*;
+
+	assert_eq!(Kitty([0; 16]).phenotype(), Phenotype {
+		gender: KittyGender::Male,
+		fur_colour: FurColour::Ginger,
+		fur_pattern: FurPattern::Solid,
+		eye_colour: EyeColour::Green,
+		eye_shape: EyeShape::Round,
+		body_type: BodyType::Cobby,
+		rarity: Rarity::Common,
+	});
+	assert_eq!(Kitty([1, 0x20, 0x5f, 0xe0, 0x80, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).phenotype(), Phenotype {
+		gender: KittyGender::Female,
+		fur_colour: FurColour::Black,
+		fur_pattern: FurPattern::Bicolour,
+		eye_colour: EyeColour::Odd,
+		eye_shape: EyeShape::Sleepy,
+		body_type: BodyType::Chonky,
+		rarity: Rarity::Rare,
+	});
+	// recessive alleles are not visible
+	assert_eq!(Kitty([0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]).phenotype().rarity, Rarity::Common);
+	assert_eq!(Kitty([0, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).phenotype().rarity, Rarity::Legendary);
+}
+
+#[test]
+fn mendelian_breeding() {
+	use crate::phenotype::FurColour;
+
+	let mut dna1 = [0u8; 16];
+	dna1[0] = 1;
+	dna1[1] = 0xe0;
+	dna1[9] = 0xe0;
+	let mut dna2 = [0u8; 16];
+	dna2[1] = 0x20;
+	dna2[9] = 0xe0;
+	let (kitty1, kitty2) = (Kitty(dna1), Kitty(dna2));
+
+	assert_eq!(MendelianBreeding::breed(&kitty1, &kitty1, [0; 16]), Err(BreedingError::SameGender));
+
+	// rare fur colour is recessive
+	let kitten = MendelianBreeding::breed(&kitty1, &kitty2, [0; 16]).unwrap();
+	assert_eq!(kitten.phenotype().fur_colour, FurColour::Black);
+	assert_eq!(kitten.0[9], 0xe0);
+
+	// both alleles are rare
+	let mut seed = [0u8; 16];
+	seed[1] = 2;
+	let kitten = MendelianBreeding::breed(&kitty1, &kitty2, seed).unwrap();
+	assert_eq!(kitten.phenotype().fur_colour, FurColour::Lilac);
+}
+
 #[test]
 fn can_breed() {
 	new_test_ext().execute_with(|| {
@@ -171,12 +293,84 @@ fn can_breed() {
 
 		assert_ok!(KittiesModule::breed(Origin::signed(100), 0, 1));
 
+		System::assert_last_event(Event::KittiesModule(crate::Event::<Test>::Pregnant(100u64, 0u32, 100u64, 1u32, 100u64, 3u64)));
+		assert_eq!(KittiesModule::pregnancies(0).map(|pregnancy| pregnancy.due), Some(3));
+		assert_eq!(KittiesModule::pregnancies(1), None);
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 2), None);
+
+		run_to_block(2);
+
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 2), None);
+
+		run_to_block(3);
+
 		let kitty = Kitty([187, 250, 235, 118, 211, 247, 237, 253, 187, 239, 191, 185, 239, 171, 211, 122]);
 
 		assert_eq!(KittiesModule::kitties(&100, 2), Some(kitty.clone()));
 		assert_eq!(Nft::tokens(KittiesModule::class_id(), 2).unwrap().owner, 100);
+		assert_eq!(KittiesModule::pregnancies(0), None);
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::<Test>::KittyBred(100u64, 2u32, kitty, 100u64, 100u64)));
+	});
+}
+
+#[test]
+fn pregnant_kitty_is_locked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		MockRandom::set(H256::from([2; 32]));
+
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(100), 0, Some(10)));
+
+		assert_ok!(KittiesModule::breed(Origin::signed(100), 0, 1));
+
+		assert_noop!(KittiesModule::breed(Origin::signed(100), 0, 1), Error::<Test>::KittyPregnant);
+		assert_noop!(KittiesModule::transfer(Origin::signed(100), 200, 0), Error::<Test>::KittyPregnant);
+		assert_noop!(KittiesModule::set_price(Origin::signed(100), 0, Some(20)), Error::<Test>::KittyPregnant);
+		assert_noop!(KittiesModule::buy(Origin::signed(200), 100, 0, 10), Error::<Test>::KittyPregnant);
+		assert_noop!(KittiesModule::burn(Origin::signed(100), 0), Error::<Test>::KittyPregnant);
+
+		// the father is not locked
+		assert_ok!(KittiesModule::transfer(Origin::signed(100), 200, 1));
+
+		run_to_block(3);
+
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 2).unwrap().owner, 100);
+		assert_ok!(KittiesModule::transfer(Origin::signed(100), 200, 0));
+	});
+}
+
+#[test]
+fn can_mutate() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		MockRandom::set(H256::from([2; 32]));
+
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_noop!(KittiesModule::set_mutation_rate(Origin::signed(100), Some(Perbill::one())), BadOrigin);
+
+		assert_ok!(KittiesModule::set_mutation_rate(Origin::root(), Some(Perbill::one())));
+		System::assert_last_event(Event::KittiesModule(crate::Event::MutationRateUpdated(Some(Perbill::one()))));
+		assert_eq!(KittiesModule::mutation_rate(), Perbill::one());
+
+		assert_ok!(KittiesModule::breed(Origin::signed(100), 0, 1));
+		run_to_block(3);
+
+		// same as can_breed, with one bit of every gene flipped
+		let unmutated = [187, 250, 235, 118, 211, 247, 237, 253, 187, 239, 191, 185, 239, 171, 211, 122u8];
+		let kitty = KittiesModule::kitties(&100, 2).unwrap();
+		for (gene, unmutated_gene) in kitty.0.iter().zip(unmutated.iter()) {
+			assert_eq!((gene ^ unmutated_gene).count_ones(), 1);
+		}
 
-		System::assert_last_event(Event::KittiesModule(crate::Event::<Test>::KittyBred(100u64, 2u32, kitty)));
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyMutated(2, (0..16).collect())));
+
+		assert_ok!(KittiesModule::set_mutation_rate(Origin::root(), None));
+		assert_eq!(KittiesModule::mutation_rate(), Perbill::from_percent(0));
 	});
 }
 
@@ -263,58 +457,1169 @@ fn can_buy() {
 	});
 }
 
+/// The first solution of an auto breed of `kitty_id_1` and `kitty_id_2` with `nonce`.
+fn solve(kitty_id_1: u32, kitty_id_2: u32, nonce: u32) -> u128 {
+	(0..).find(|solution| KittiesModule::validate_solution(kitty_id_1, kitty_id_2, nonce, *solution)).unwrap()
+}
+
 #[test]
 fn can_auto_breed() {
 	new_test_ext().execute_with(|| {
-		// nonce and solution are not checked by auto_breed directly
-
 		assert_ok!(KittiesModule::create(Origin::signed(100)));
 		assert_ok!(KittiesModule::create(Origin::signed(101)));
 
-		assert_noop!(KittiesModule::auto_breed(Origin::none(), 0, 2, 0, 0), Error::<Test>::InvalidKittyId);
-		assert_noop!(KittiesModule::auto_breed(Origin::none(), 0, 0, 0, 0), Error::<Test>::SameGender);
-		assert_noop!(KittiesModule::auto_breed(Origin::signed(100), 0, 1, 0, 0), BadOrigin);
+		assert_ok!(KittiesModule::set_auto_breed(Origin::signed(100), 0, true));
+
+		assert_noop!(KittiesModule::auto_breed(Origin::none(), 0, 1, 0, solve(0, 1, 0)), Error::<Test>::AutoBreedNotEnabled);
+
+		assert_ok!(KittiesModule::set_auto_breed(Origin::signed(101), 1, true));
+
+		assert_noop!(KittiesModule::auto_breed(Origin::none(), 0, 2, 0, solve(0, 2, 0)), Error::<Test>::InvalidKittyId);
+		assert_noop!(KittiesModule::auto_breed(Origin::none(), 0, 0, 0, solve(0, 0, 0)), Error::<Test>::SameGender);
+		assert_noop!(KittiesModule::auto_breed(Origin::signed(100), 0, 1, 0, solve(0, 1, 0)), BadOrigin);
+		assert_noop!(KittiesModule::auto_breed(Origin::none(), 0, 1, 0, 0), Error::<Test>::InvalidSolution);
+		assert_noop!(KittiesModule::auto_breed(Origin::none(), 0, 1, 1, solve(0, 1, 1)), Error::<Test>::InvalidNonce);
+
+		assert_ok!(KittiesModule::auto_breed(Origin::none(), 0, 1, 0, solve(0, 1, 0)));
+
+		// the hash of the pair gives the owner of the kitty with the lower id the first kitten
+		System::assert_last_event(Event::KittiesModule(crate::Event::Pregnant(100, 0, 100, 1, 101, 3)));
+
+		// the nonce is shared by both orders of the pair
+		assert_eq!(KittiesModule::auto_breed_nonce(0, 1), 1);
+		assert_eq!(KittiesModule::auto_breed_nonce(1, 0), 1);
+		assert_noop!(KittiesModule::auto_breed(Origin::none(), 1, 0, 0, solve(1, 0, 0)), Error::<Test>::InvalidNonce);
 
-		assert_ok!(KittiesModule::auto_breed(Origin::none(), 0, 1, 0, 0));
+		run_to_block(3);
 
 		let kitty = Kitty([34, 170, 2, 80, 145, 37, 4, 36, 35, 32, 179, 144, 169, 40, 2, 18]);
 
 		assert_eq!(KittiesModule::kitties(&100, 2), Some(kitty.clone()));
 		assert_eq!(Nft::tokens(KittiesModule::class_id(), 2).unwrap().owner, 100);
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 2).unwrap().data.source, KittySource::AutoBred);
 
-		System::assert_last_event(Event::KittiesModule(crate::Event::KittyBred(100, 2, kitty)));
+		// the birth reports the owners of both parents
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyBred(100, 2, kitty, 100, 101)));
+
+		run_to_block(6);
+
+		// the owners take turns regardless of the order of the kitties
+		assert_ok!(KittiesModule::auto_breed(Origin::none(), 1, 0, 1, solve(1, 0, 1)));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::Pregnant(101, 0, 100, 1, 101, 8)));
+
+		run_to_block(8);
+
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 3).unwrap().owner, 101);
+		assert!(System::events().iter().any(|record| matches!(record.event, Event::KittiesModule(crate::Event::KittyBred(101, 3, _, 100, 101)))));
+
+		// the hash of another pair gives the owner of the kitty with the higher id the first kitten
+		assert_eq!(KittiesModule::auto_breed_owner((2, &100), (3, &101), 0), 101);
+		assert_eq!(KittiesModule::auto_breed_owner((3, &101), (2, &100), 0), 101);
+		assert_eq!(KittiesModule::auto_breed_owner((2, &100), (3, &101), 1), 100);
 	});
 }
 
 #[test]
 fn can_validate_unsigned() {
 	new_test_ext().execute_with(|| {
-		// only check nonce and solution are valid
-
-		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 0, 0)), InvalidTransaction::BadProof.into());
-		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 0, 1)), InvalidTransaction::BadProof.into());
-		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 0, 2)), TransactionValidity::Ok(ValidTransaction {
+		// kitties 0 and 2 are female, kitties 1 and 3 are male
+		for kitty_id in 0..4 {
+			MockRandom::set(H256::from([(kitty_id % 2) as u8 * 2; 32]));
+			assert_ok!(KittiesModule::create(Origin::signed(100)));
+			assert_ok!(KittiesModule::set_auto_breed(Origin::signed(100), kitty_id, true));
+		}
+
+		let valid = |kitty_id_1: u32, kitty_id_2: u32, nonce: u32| TransactionValidity::Ok(ValidTransaction {
 			priority: 0,
 			requires: vec![],
-			provides: vec![],
+			provides: vec![("kitties", ((kitty_id_1, kitty_id_2), nonce)).encode()],
 			longevity: 64,
 			propagate: true,
-		}));
+		});
+
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 0, 0)), InvalidTransaction::BadProof.into());
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 0, 1)), InvalidTransaction::BadProof.into());
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 0, 2)), valid(0, 1, 0));
+
+		// validation has no side effects
+		assert_eq!(KittiesModule::auto_breed_nonce(0, 1), 0);
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 0, 2)), valid(0, 1, 0));
 
-		assert_eq!(KittiesModule::auto_breed_nonce(), 1);
+		// independent pairs are valid at the same time
+		let solution = solve(2, 3, 0);
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(2, 3, 0, solution)), valid(2, 3, 0));
+		let solution = solve(1, 0, 0);
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(1, 0, 0, solution)), valid(0, 1, 0));
 
-		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 0, 2)), InvalidTransaction::BadProof.into());
+		AutoBreedNonces::<Test>::insert((0, 1), 1);
+
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 0, 2)), InvalidTransaction::Stale.into());
 
 		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 1, 11)), InvalidTransaction::BadProof.into());
 		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 1, 12)), InvalidTransaction::BadProof.into());
-		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 1, 13)), TransactionValidity::Ok(ValidTransaction {
-			priority: 0,
-			requires: vec![],
-			provides: vec![],
-			longevity: 64,
-			propagate: true,
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 1, 13)), valid(0, 1, 1));
+
+		// the kitten owner must be able to hold the kitten
+		KittyDeposit::set(5);
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 1, 13)), InvalidTransaction::Custom(crate::KITTEN_OWNER_CANNOT_HOLD).into());
+		KittyDeposit::set(0);
+
+		MaxKittiesPerAccount::set(4);
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 1, 13)), InvalidTransaction::Custom(crate::KITTEN_OWNER_CANNOT_HOLD).into());
+		MaxKittiesPerAccount::set(10);
+
+		assert_ok!(KittiesModule::set_auto_breed(Origin::signed(100), 1, false));
+
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 1, 1, 13)), InvalidTransaction::Call.into());
+
+		// pairs that cannot breed are rejected
+		let solution = solve(0, 4, 0);
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 4, 0, solution)), InvalidTransaction::Call.into());
+		let solution = solve(0, 2, 0);
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(0, 2, 0, solution)), InvalidTransaction::Call.into());
+
+		assert_ok!(KittiesModule::breed(Origin::signed(100), 2, 3));
+
+		let solution = solve(2, 3, 0);
+		assert_eq!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &crate::Call::auto_breed(2, 3, 0, solution)), InvalidTransaction::Call.into());
+	});
+}
+
+#[test]
+fn can_query_kitties() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_eq!(KittiesModule::kitties_of(&100, 0, 10), vec![0, 2, 3]);
+		assert_eq!(KittiesModule::kitties_of(&100, 1, 10), vec![2, 3]);
+		assert_eq!(KittiesModule::kitties_of(&100, 0, 2), vec![0, 2]);
+		assert_eq!(KittiesModule::kitties_of(&200, 0, 10), vec![1]);
+		assert_eq!(KittiesModule::kitties_of(&300, 0, 10), Vec::<u32>::new());
+
+		assert_ok!(KittiesModule::set_price(Origin::signed(100), 3, Some(30)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(100), 0, Some(10)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(200), 1, Some(20)));
+
+		let kitty = Nft::tokens(KittiesModule::class_id(), 1).unwrap().data.dna;
+		assert_eq!(KittiesModule::kitty_info(1), Some(KittyInfo {
+			owner: 200,
+			gender: kitty.gender(),
+			phenotype: kitty.phenotype(),
+			dna: kitty,
+			price: Some(20),
+			metadata: None,
 		}));
+		assert_eq!(KittiesModule::kitty_info(2).unwrap().price, None);
+		assert_eq!(KittiesModule::kitty_info(4), None);
+
+		assert_eq!(KittiesModule::listed_kitties(0, 10), vec![(0, 10), (1, 20), (3, 30)]);
+		assert_eq!(KittiesModule::listed_kitties(1, 1), vec![(1, 20)]);
+		assert_eq!(KittiesModule::listed_kitties(4, 10), vec![]);
+		assert_eq!(KittiesModule::listed_kitties(2, 10), vec![(3, 30)]);
+
+		assert_ok!(KittiesModule::set_price(Origin::signed(200), 1, None));
+
+		assert_eq!(KittiesModule::listed_kitties(0, 10), vec![(0, 10), (3, 30)]);
+	});
+}
+
+#[test]
+fn can_auction() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(100), 0, Some(10)));
+
+		assert_noop!(KittiesModule::create_auction(Origin::signed(200), 0, 100, 10, 5), Error::<Test>::NotOwner);
+		assert_noop!(KittiesModule::create_auction(Origin::signed(100), 0, 100, 10, 1), Error::<Test>::InvalidAuctionEnd);
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(100), 0, 100, 10, 5));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::AuctionCreated(100, 0, 100, 10, 5)));
+
+		assert_eq!(KittyPrices::<Test>::contains_key(0), false);
+
+		assert_noop!(KittiesModule::create_auction(Origin::signed(100), 0, 100, 10, 5), Error::<Test>::KittyInAuction);
+		assert_noop!(KittiesModule::transfer(Origin::signed(100), 200, 0), Error::<Test>::KittyInAuction);
+		assert_noop!(KittiesModule::set_price(Origin::signed(100), 0, Some(10)), Error::<Test>::KittyInAuction);
+
+		assert_noop!(KittiesModule::bid(Origin::signed(200), 1, 100), Error::<Test>::AuctionNotFound);
+		assert_noop!(KittiesModule::bid(Origin::signed(100), 0, 100), Error::<Test>::BidOnOwnAuction);
+		assert_noop!(KittiesModule::bid(Origin::signed(200), 0, 99), Error::<Test>::BidTooLow);
+
+		assert_ok!(KittiesModule::bid(Origin::signed(200), 0, 100));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::AuctionBid(200, 0, 100)));
+		assert_eq!(Balances::reserved_balance(200), 100);
+
+		assert_noop!(KittiesModule::bid(Origin::signed(300), 0, 109), Error::<Test>::BidTooLow);
+		assert_noop!(KittiesModule::cancel_auction(Origin::signed(100), 0), Error::<Test>::AuctionHasBids);
+
+		assert_ok!(KittiesModule::bid(Origin::signed(300), 0, 110));
+
+		assert_eq!(Balances::reserved_balance(200), 0);
+		assert_eq!(Balances::reserved_balance(300), 110);
+
+		System::set_block_number(5);
+
+		assert_noop!(KittiesModule::bid(Origin::signed(200), 0, 200), Error::<Test>::AuctionEnded);
+
+		KittiesModule::on_initialize(5);
+
+		assert_eq!(KittiesModule::auctions(0), None);
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 0).unwrap().owner, 300);
+		assert_eq!(Balances::reserved_balance(300), 0);
+		assert_eq!(Balances::free_balance(300), 390);
+		assert_eq!(Balances::free_balance(100), 110);
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::AuctionSold(100, 300, 0, 110)));
+	});
+}
+
+#[test]
+fn can_cancel_auction() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(100), 0, 100, 10, 5));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(100), 1, 100, 10, 5));
+
+		assert_noop!(KittiesModule::create_auction(Origin::signed(100), 2, 100, 10, 5), Error::<Test>::TooManyExpiries);
+
+		assert_noop!(KittiesModule::cancel_auction(Origin::signed(200), 0), Error::<Test>::NotOwner);
+		assert_noop!(KittiesModule::cancel_auction(Origin::signed(100), 2), Error::<Test>::AuctionNotFound);
+
+		assert_ok!(KittiesModule::cancel_auction(Origin::signed(100), 0));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::AuctionCancelled(100, 0)));
+
+		assert_eq!(KittiesModule::auctions(0), None);
+		assert_eq!(*AuctionEndAt::<Test>::get(5), vec![1]);
+		assert_ok!(KittiesModule::transfer(Origin::signed(100), 200, 0));
+
+		// auction without bids expires
+		KittiesModule::on_initialize(5);
+
+		assert_eq!(KittiesModule::auctions(1), None);
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 1).unwrap().owner, 100);
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::AuctionExpired(100, 1)));
+	});
+}
+
+#[test]
+fn dutch_listing_price() {
+	let listing = DutchListing { start_price: 400u64, end_price: 100u64, start: 1u64, duration: 10u64 };
+
+	assert_eq!(listing.price_at(0), 400);
+	assert_eq!(listing.price_at(1), 400);
+	assert_eq!(listing.price_at(2), 370);
+	assert_eq!(listing.price_at(6), 250);
+	assert_eq!(listing.price_at(10), 130);
+	assert_eq!(listing.price_at(11), 100);
+	assert_eq!(listing.price_at(100), 100);
+}
+
+#[test]
+fn can_buy_dutch() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(100), 0, Some(10)));
+
+		assert_noop!(KittiesModule::list_dutch(Origin::signed(200), 0, 400, 100, 10), Error::<Test>::NotOwner);
+		assert_noop!(KittiesModule::list_dutch(Origin::signed(100), 0, 100, 400, 10), Error::<Test>::InvalidDutchListing);
+		assert_noop!(KittiesModule::list_dutch(Origin::signed(100), 0, 400, 100, 0), Error::<Test>::InvalidDutchListing);
+
+		assert_ok!(KittiesModule::list_dutch(Origin::signed(100), 0, 400, 100, 10));
+		assert_ok!(KittiesModule::list_dutch(Origin::signed(100), 1, 400, 100, 10));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyDutchListed(100, 1, 400, 100, 10)));
+
+		assert_eq!(KittyPrices::<Test>::contains_key(0), false);
+		assert_eq!(KittiesModule::current_price(0), Some(400));
+
+		System::set_block_number(6);
+
+		assert_eq!(KittiesModule::current_price(0), Some(250));
+		assert_eq!(KittiesModule::listed_kitties(0, 10), vec![(0, 250), (1, 250)]);
+
+		assert_noop!(KittiesModule::buy(Origin::signed(200), 100, 0, 249), Error::<Test>::PriceTooLow);
+
+		assert_ok!(KittiesModule::buy(Origin::signed(200), 100, 0, 300));
+
+		assert_eq!(KittiesModule::dutch_listings(0), None);
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 0).unwrap().owner, 200);
+		assert_eq!(Balances::free_balance(100), 250);
+		assert_eq!(Balances::free_balance(200), 250);
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittySold(100, 200, 0, 250)));
+
+		// fixed price replaces dutch listing
+		assert_ok!(KittiesModule::set_price(Origin::signed(100), 1, Some(20)));
+
+		assert_eq!(KittiesModule::dutch_listings(1), None);
+		assert_eq!(KittiesModule::current_price(1), Some(20));
+
+		assert_ok!(KittiesModule::set_price(Origin::signed(100), 1, None));
+
+		assert_eq!(KittiesModule::current_price(1), None);
+	});
+}
+
+#[test]
+fn can_make_offer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_noop!(KittiesModule::make_offer(Origin::signed(200), 1, 100, 5), Error::<Test>::InvalidKittyId);
+		assert_noop!(KittiesModule::make_offer(Origin::signed(100), 0, 100, 5), Error::<Test>::BuyFromSelf);
+		assert_noop!(KittiesModule::make_offer(Origin::signed(200), 0, 0, 5), Error::<Test>::InvalidOfferAmount);
+		assert_noop!(KittiesModule::make_offer(Origin::signed(200), 0, 100, 1), Error::<Test>::InvalidOfferExpiry);
+		assert_noop!(KittiesModule::make_offer(Origin::signed(200), 0, 600, 5), pallet_balances::Error::<Test, _>::InsufficientBalance);
+
+		assert_ok!(KittiesModule::make_offer(Origin::signed(200), 0, 100, 5));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::OfferMade(200, 0, 100, 5)));
+		assert_eq!(Balances::reserved_balance(200), 100);
+
+		// replace the offer
+		assert_ok!(KittiesModule::make_offer(Origin::signed(200), 0, 150, 6));
+
+		assert_eq!(KittiesModule::offers(0, 200), Some(Offer { amount: 150, deposit: 0, expiry: 6 }));
+		assert_eq!(Balances::reserved_balance(200), 150);
+
+		assert_ok!(KittiesModule::make_offer(Origin::signed(300), 0, 100, 5));
+		assert_noop!(KittiesModule::make_offer(Origin::signed(400), 0, 100, 5), Error::<Test>::TooManyOffers);
+
+		assert_noop!(KittiesModule::withdraw_offer(Origin::signed(400), 0), Error::<Test>::OfferNotFound);
+		assert_ok!(KittiesModule::withdraw_offer(Origin::signed(300), 0));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::OfferWithdrawn(300, 0)));
+		assert_eq!(Balances::reserved_balance(300), 0);
+
+		// the old expiry is removed when replacing the offer
+		KittiesModule::on_initialize(5);
+		assert_eq!(Balances::reserved_balance(200), 150);
+
+		KittiesModule::on_initialize(6);
+
+		assert_eq!(KittiesModule::offers(0, 200), None);
+		assert_eq!(Balances::reserved_balance(200), 0);
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::OfferExpired(200, 0)));
+	});
+}
+
+#[test]
+fn can_accept_offer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_ok!(KittiesModule::make_offer(Origin::signed(200), 0, 100, 5));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(300), 0, 200, 5));
+
+		assert_noop!(KittiesModule::accept_offer(Origin::signed(200), 0, 300), Error::<Test>::NotOwner);
+		assert_noop!(KittiesModule::accept_offer(Origin::signed(100), 0, 400), Error::<Test>::OfferNotFound);
+
+		assert_ok!(KittiesModule::accept_offer(Origin::signed(100), 0, 300));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::OfferAccepted(100, 300, 0, 200)));
+
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 0).unwrap().owner, 300);
+		assert_eq!(Balances::free_balance(100), 200);
+		assert_eq!(Balances::free_balance(300), 300);
+		assert_eq!(Balances::reserved_balance(300), 0);
+
+		// other offers are invalidated
+		assert_eq!(KittiesModule::offers(0, 200), None);
+		assert_eq!(Balances::reserved_balance(200), 0);
+		assert_eq!(Balances::free_balance(200), 500);
+	});
+}
+
+#[test]
+fn transfer_invalidates_offers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_ok!(KittiesModule::make_offer(Origin::signed(200), 0, 100, 5));
+
+		assert_ok!(KittiesModule::transfer(Origin::signed(100), 300, 0));
+
+		assert_eq!(KittiesModule::offers(0, 200), None);
+		assert_eq!(OfferExpiryAt::<Test>::contains_key(5), false);
+		assert_eq!(Balances::reserved_balance(200), 0);
+	});
+}
+
+#[test]
+fn offers_and_bids_reserve_kitty_deposit() {
+	new_test_ext().execute_with(|| {
+		KittyDeposit::set(5);
+
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+
+		assert_ok!(KittiesModule::make_offer(Origin::signed(300), 0, 100, 5));
+
+		assert_eq!(KittiesModule::offers(0, 300), Some(Offer { amount: 100, deposit: 5, expiry: 5 }));
+		assert_eq!(Balances::reserved_balance(300), 105);
+
+		assert_ok!(KittiesModule::accept_offer(Origin::signed(200), 0, 300));
+
+		assert_eq!(KittiesModule::kitty_deposits(0), Some((300, 5)));
+		assert_eq!(Balances::reserved_balance(300), 5);
+		assert_eq!(Balances::free_balance(300), 395);
+		assert_eq!(Balances::reserved_balance(200), 5);
+		assert_eq!(Balances::free_balance(200), 595);
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(200), 1, 100, 10, 5));
+		assert_noop!(KittiesModule::bid(Origin::signed(300), 1, 391), pallet_balances::Error::<Test, _>::InsufficientBalance);
+		assert_ok!(KittiesModule::bid(Origin::signed(300), 1, 390));
+
+		assert_eq!(Balances::reserved_balance(300), 400);
+		assert_eq!(Balances::free_balance(300), 0);
+
+		System::set_block_number(5);
+		KittiesModule::on_initialize(5);
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::AuctionSold(200, 300, 1, 390)));
+		assert_eq!(KittiesModule::kitty_deposits(1), Some((300, 5)));
+		assert_eq!(Balances::reserved_balance(300), 10);
+		assert_eq!(Balances::free_balance(300), 0);
+		assert_eq!(Balances::reserved_balance(200), 0);
+		assert_eq!(Balances::free_balance(200), 990);
+	});
+}
+
+#[test]
+fn offer_expiries_are_capped() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_ok!(KittiesModule::make_offer(Origin::signed(200), 0, 10, 5));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(300), 0, 10, 5));
+
+		assert_noop!(KittiesModule::make_offer(Origin::signed(200), 1, 10, 5), Error::<Test>::TooManyExpiries);
+
+		assert_ok!(KittiesModule::withdraw_offer(Origin::signed(300), 0));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(200), 1, 10, 5));
+
+		assert_eq!(*OfferExpiryAt::<Test>::get(5), vec![(0, 200), (1, 200)]);
+	});
+}
+
+#[test]
+fn can_set_royalty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_eq!(KittiesModule::kitty_creators(0), Some(100));
+		assert_eq!(KittiesModule::royalty(0), Perbill::from_percent(10));
+
+		assert_noop!(KittiesModule::set_royalty(Origin::signed(200), 0, Some(Perbill::from_percent(20))), Error::<Test>::NotCreator);
+		assert_noop!(KittiesModule::set_royalty(Origin::signed(100), 0, Some(Perbill::from_percent(60))), Error::<Test>::RoyaltyTooHigh);
+
+		assert_ok!(KittiesModule::set_royalty(Origin::signed(100), 0, Some(Perbill::from_percent(20))));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::RoyaltyUpdated(100, 0, Some(Perbill::from_percent(20)))));
+		assert_eq!(KittiesModule::royalty(0), Perbill::from_percent(20));
+
+		assert_ok!(KittiesModule::set_royalty(Origin::signed(100), 0, None));
+		assert_eq!(KittiesModule::royalty(0), Perbill::from_percent(10));
+
+		assert_ok!(KittiesModule::transfer(Origin::signed(100), 200, 0));
+
+		assert_noop!(KittiesModule::set_royalty(Origin::signed(100), 0, Some(Perbill::from_percent(20))), Error::<Test>::NotOwner);
+	});
+}
+
+#[test]
+fn can_pay_royalty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::set_royalty(Origin::signed(100), 0, Some(Perbill::from_percent(20))));
+
+		// no royalty on primary sale
+		assert_ok!(KittiesModule::set_price(Origin::signed(100), 0, Some(100)));
+		assert_ok!(KittiesModule::buy(Origin::signed(200), 100, 0, 100));
+
+		assert_eq!(Balances::free_balance(100), 100);
+		assert_eq!(Balances::free_balance(200), 400);
+
+		assert_ok!(KittiesModule::set_price(Origin::signed(200), 0, Some(200)));
+		assert_ok!(KittiesModule::buy(Origin::signed(300), 200, 0, 200));
+
+		assert_eq!(Balances::free_balance(100), 140);
+		assert_eq!(Balances::free_balance(200), 560);
+		assert_eq!(Balances::free_balance(300), 300);
+
+		assert!(System::events().iter().any(|record| record.event == Event::KittiesModule(crate::Event::RoyaltyPaid(100, 0, 40))));
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittySold(200, 300, 0, 200)));
+	});
+}
+
+#[test]
+fn royalty_below_existential_deposit_goes_to_seller() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::set_royalty(Origin::signed(100), 0, Some(Perbill::from_percent(2))));
+		assert_ok!(KittiesModule::transfer(Origin::signed(100), 200, 0));
+
+		ExistentialDeposit::set(5);
+
+		assert_ok!(KittiesModule::set_price(Origin::signed(200), 0, Some(100)));
+		assert_ok!(KittiesModule::buy(Origin::signed(300), 200, 0, 100));
+
+		assert_eq!(Balances::free_balance(100), 0);
+		assert_eq!(Balances::free_balance(200), 600);
+		assert_eq!(Balances::free_balance(300), 400);
+
+		assert!(!System::events().iter().any(|record| matches!(record.event, Event::KittiesModule(crate::Event::RoyaltyPaid(..)))));
+	});
+}
+
+#[test]
+fn can_track_pedigree() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		MockRandom::set(H256::from([2; 32]));
+
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_eq!(KittiesModule::pedigrees(0), Some(Pedigree { parents: None, generation: 0, birth: 1 }));
+
+		run_to_block(2);
+
+		assert_ok!(KittiesModule::breed(Origin::signed(100), 0, 1));
+
+		run_to_block(4);
+
+		assert_eq!(KittiesModule::pedigrees(2), Some(Pedigree { parents: Some((0, 1)), generation: 1, birth: 4 }));
+
+		run_to_block(7);
+
+		assert_ok!(KittiesModule::breed(Origin::signed(100), 2, 1));
+
+		run_to_block(9);
+
+		assert_eq!(KittiesModule::pedigrees(3), Some(Pedigree { parents: Some((2, 1)), generation: 2, birth: 9 }));
+		assert_eq!(KittiesModule::generation(3), 2);
+
+		assert_eq!(KittiesModule::ancestors(3, 8), vec![(2, 1), (1, 1), (0, 2)]);
+		assert_eq!(KittiesModule::ancestors(3, 1), vec![(2, 1), (1, 1)]);
+		assert_eq!(KittiesModule::ancestors(0, 8), vec![]);
+	});
+}
+
+#[test]
+fn breeding_cooldown_and_limit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		MockRandom::set(H256::from([2; 32]));
+
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::set_auto_breed(Origin::signed(100), 0, true));
+		assert_ok!(KittiesModule::set_auto_breed(Origin::signed(100), 1, true));
+
+		assert_ok!(KittiesModule::breed(Origin::signed(100), 0, 1));
+
+		assert_eq!(KittiesModule::breeding_states(0), BreedingState { breed_count: 1, cooldown_end: 6 });
+		assert_eq!(KittiesModule::breeding_states(1), BreedingState { breed_count: 1, cooldown_end: 6 });
+
+		run_to_block(3);
+
+		assert_noop!(KittiesModule::breed(Origin::signed(100), 0, 1), Error::<Test>::KittyOnCooldown);
+		assert_noop!(KittiesModule::auto_breed(Origin::none(), 0, 1, 0, solve(0, 1, 0)), Error::<Test>::KittyOnCooldown);
+
+		run_to_block(6);
+
+		assert_ok!(KittiesModule::breed(Origin::signed(100), 0, 1));
+
+		assert_eq!(KittiesModule::breeding_states(0), BreedingState { breed_count: 2, cooldown_end: 16 });
+
+		run_to_block(15);
+
+		assert_noop!(KittiesModule::breed(Origin::signed(100), 0, 1), Error::<Test>::KittyOnCooldown);
+
+		run_to_block(16);
+
+		assert_ok!(KittiesModule::breed(Origin::signed(100), 0, 1));
+
+		// the last cooldown applies to further breeding
+		assert_eq!(KittiesModule::breeding_states(0), BreedingState { breed_count: 3, cooldown_end: 26 });
+
+		run_to_block(100);
+
+		assert_noop!(KittiesModule::breed(Origin::signed(100), 0, 1), Error::<Test>::BreedLimitReached);
+		assert_noop!(KittiesModule::auto_breed(Origin::none(), 0, 1, 0, solve(0, 1, 0)), Error::<Test>::BreedLimitReached);
+	});
+}
+
+#[test]
+fn can_breed_with_sire() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		MockRandom::set(H256::from([2; 32]));
+
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+
+		assert_noop!(KittiesModule::offer_siring(Origin::signed(100), 1, Some(50)), Error::<Test>::NotOwner);
+		assert_noop!(KittiesModule::offer_siring(Origin::signed(100), 0, Some(50)), Error::<Test>::SireNotMale);
+		assert_noop!(KittiesModule::breed_with_sire(Origin::signed(100), 0, 1, 50), Error::<Test>::SireNotOffered);
+
+		assert_ok!(KittiesModule::offer_siring(Origin::signed(200), 1, Some(50)));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::SiringOffered(200, 1, Some(50))));
+		assert_eq!(KittiesModule::siring_fees(1), Some(50));
+
+		assert_noop!(KittiesModule::breed_with_sire(Origin::signed(200), 0, 1, 50), Error::<Test>::InvalidKittyId);
+		assert_noop!(KittiesModule::breed_with_sire(Origin::signed(200), 2, 1, 50), Error::<Test>::BuyFromSelf);
+		assert_noop!(KittiesModule::breed_with_sire(Origin::signed(100), 0, 1, 49), Error::<Test>::FeeTooLow);
+		assert_noop!(KittiesModule::breed_with_sire(Origin::signed(100), 0, 1, 50), pallet_balances::Error::<Test, _>::InsufficientBalance);
+
+		assert_ok!(Balances::transfer(Origin::signed(300), 100, 100));
+
+		assert_ok!(KittiesModule::breed_with_sire(Origin::signed(100), 0, 1, 50));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::SiringUsed(100, 200, 1, 50)));
+
+		run_to_block(3);
+
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 3).unwrap().owner, 100);
+		assert_eq!(KittiesModule::pedigrees(3).unwrap().parents, Some((0, 1)));
+		assert_eq!(Balances::free_balance(100), 50);
+		assert_eq!(Balances::free_balance(200), 550);
+
+		assert_ok!(KittiesModule::offer_siring(Origin::signed(200), 1, None));
+
+		assert_eq!(KittiesModule::siring_fees(1), None);
+	});
+}
+
+#[test]
+fn can_burn() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_ok!(KittiesModule::set_price(Origin::signed(100), 0, Some(10)));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(200), 0, 100, 5));
+
+		assert_noop!(KittiesModule::burn(Origin::signed(200), 0), orml_nft::Error::<Test>::NoPermission);
+
+		assert_ok!(KittiesModule::burn(Origin::signed(100), 0));
+
+		// no refund when the pallet account cannot pay
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyBurned(100, 0, 0)));
+
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 0), None);
+		assert_eq!(KittyPrices::<Test>::contains_key(0), false);
+		assert_eq!(KittiesModule::offers(0, 200), None);
+		assert_eq!(Balances::reserved_balance(200), 0);
+		assert_eq!(KittiesModule::kitty_creators(0), None);
+		assert!(KittiesModule::pedigrees(0).is_some());
+
+		assert_noop!(KittiesModule::burn(Origin::signed(100), 0), orml_nft::Error::<Test>::TokenNotFound);
+
+		assert_ok!(Balances::transfer(Origin::signed(300), KittiesModule::account_id(), 100));
+
+		assert_ok!(KittiesModule::burn(Origin::signed(100), 1));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyBurned(100, 1, 10)));
+		assert_eq!(Balances::free_balance(100), 10);
+		assert_eq!(Balances::free_balance(KittiesModule::account_id()), 90);
+	});
+}
+
+#[test]
+fn can_set_metadata() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+
+		assert_noop!(KittiesModule::set_metadata(Origin::signed(100), 0, b"kitty".to_vec(), vec![]), Error::<Test>::NotOwner);
+		assert_noop!(KittiesModule::set_metadata(Origin::signed(200), 0, vec![0; 9], vec![]), Error::<Test>::NameTooLong);
+		assert_noop!(KittiesModule::set_metadata(Origin::signed(200), 0, vec![], vec![0; 21]), Error::<Test>::UriTooLong);
+
+		assert_ok!(KittiesModule::set_metadata(Origin::signed(200), 0, b"kitty".to_vec(), b"ipfs://cat".to_vec()));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyMetadataSet(200, 0, 25)));
+
+		assert_eq!(Balances::reserved_balance(200), 25);
+		assert_eq!(KittiesModule::kitty_info(0).unwrap().metadata, Some(KittyMetadata {
+			name: b"kitty".to_vec(),
+			uri: b"ipfs://cat".to_vec(),
+		}));
+
+		assert_ok!(KittiesModule::set_metadata(Origin::signed(200), 0, b"tom".to_vec(), vec![]));
+
+		assert_eq!(Balances::reserved_balance(200), 13);
+		assert_eq!(KittiesModule::kitty_info(0).unwrap().metadata, Some(KittyMetadata {
+			name: b"tom".to_vec(),
+			uri: vec![],
+		}));
+
+		assert_ok!(KittiesModule::set_metadata(Origin::signed(200), 0, vec![], vec![]));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyMetadataCleared(200, 0)));
+
+		assert_eq!(Balances::reserved_balance(200), 0);
+		assert_eq!(KittiesModule::metadata_deposits(0), None);
+		assert_eq!(KittiesModule::kitty_info(0).unwrap().metadata, None);
+
+		assert_ok!(KittiesModule::set_metadata(Origin::signed(200), 0, b"kitty".to_vec(), vec![]));
+		assert_ok!(KittiesModule::burn(Origin::signed(200), 0));
+
+		assert_eq!(Balances::reserved_balance(200), 0);
+	});
+}
+
+#[test]
+fn transfer_clears_metadata() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+		assert_ok!(KittiesModule::set_metadata(Origin::signed(200), 0, b"kitty".to_vec(), vec![]));
+
+		assert_eq!(Balances::reserved_balance(200), 15);
+
+		assert_ok!(KittiesModule::transfer(Origin::signed(200), 300, 0));
+
+		assert!(System::events().iter().any(|record| record.event == Event::KittiesModule(crate::Event::KittyMetadataCleared(200, 0))));
+		assert_eq!(Balances::reserved_balance(200), 0);
+		assert_eq!(KittiesModule::metadata_deposits(0), None);
+		assert_eq!(KittiesModule::kitty_info(0).unwrap().metadata, None);
+	});
+}
+
+#[test]
+fn can_build_genesis_kitties() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	<crate::GenesisConfig<Test> as GenesisBuild<Test>>::assimilate_storage(&crate::GenesisConfig {
+		kitties: vec![
+			(100, [1; 16], None),
+			(100, [2; 16], Some(10)),
+		],
+	}, &mut t).unwrap();
+
+	let mut t: sp_io::TestExternalities = t.into();
+
+	t.execute_with(|| {
+		System::set_block_number(1);
+
+		assert_eq!(KittiesModule::kitties(&100, 0), Some(Kitty([1; 16])));
+		assert_eq!(KittiesModule::kitties(&100, 1), Some(Kitty([2; 16])));
+		assert_eq!(KittiesModule::kitty_prices(0), None);
+		assert_eq!(KittiesModule::kitty_prices(1), Some(10));
+		assert_eq!(KittiesModule::kitty_creators(1), Some(100));
+		assert_eq!(KittiesModule::generation(1), 0);
+		assert_eq!(KittiesModule::gen0_minted(), 2);
+
+		assert_ok!(KittiesModule::breed(Origin::signed(100), 0, 1));
+		run_to_block(3);
+
+		assert_eq!(KittiesModule::generation(2), 1);
+	});
+}
+
+#[test]
+fn can_migrate_to_v2() {
+	new_test_ext().execute_with(|| {
+		let class_id = KittiesModule::class_id();
+		let kitties = vec![(100, Kitty([1; 16])), (100, Kitty([2; 16])), (200, Kitty([3; 16]))];
+
+		// a chain at V1 only has the tokens, with the bare dna as token data, and the prices
+		for (owner, dna) in kitties.iter() {
+			let kitty_id = Nft::mint(owner, class_id, Vec::new(), KittyRecord::default()).unwrap();
+			frame_support::storage::unhashed::put(
+				&orml_nft::Tokens::<Test>::hashed_key_for(class_id, kitty_id),
+				&orml_nft::TokenInfo {
+					metadata: Vec::<u8>::new(),
+					owner: *owner,
+					data: dna.clone(),
+				},
+			);
+		}
+		KittyPrices::<Test>::insert(1, 10);
+		StorageVersion::<Test>::put(Releases::V1);
+		Gen0BasePrice::set(10);
+		Gen0PriceIncrement::set(5);
+		frame_support::storage::unhashed::put(&crate::migrations::old_auto_breed_nonce_key::<Test>(), &7u32);
+
+		crate::migrations::migrate_to_v2::<Test>();
+
+		assert_eq!(StorageVersion::<Test>::get(), Releases::V2);
+		assert_eq!(orml_nft::Tokens::<Test>::iter_prefix(class_id).count(), 3);
+		for (kitty_id, (owner, dna)) in kitties.iter().enumerate() {
+			assert_eq!(Nft::tokens(class_id, kitty_id as u32), Some(orml_nft::TokenInfo {
+				metadata: Default::default(),
+				owner: *owner,
+				data: KittyRecord {
+					dna: dna.clone(),
+					source: KittySource::Created,
+				},
+			}));
+		}
+		assert_eq!(KittiesModule::kitty_count(100), 2);
+		assert_eq!(KittiesModule::kitty_count(200), 1);
+		assert_eq!(KittiesModule::listed_kitties(0, 10), vec![(1, 10)]);
+		// existing kitties count toward the gen-0 supply and price
+		assert_eq!(KittiesModule::gen0_minted(), 3);
+		assert_eq!(KittiesModule::remaining_supply(), 97);
+		assert_eq!(KittiesModule::mint_price(), 25);
+		assert!(!frame_support::storage::unhashed::exists(&crate::migrations::old_auto_breed_nonce_key::<Test>()));
+
+		// already migrated
+		crate::migrations::migrate_to_v2::<Test>();
+
+		assert_eq!(KittiesModule::kitty_count(100), 2);
+		assert_eq!(KittiesModule::gen0_minted(), 3);
+	});
+}
+
+#[test]
+fn can_hold_kitty_deposit() {
+	new_test_ext().execute_with(|| {
+		KittyDeposit::set(5);
+
+		assert_noop!(KittiesModule::create(Origin::signed(100)), pallet_balances::Error::<Test, _>::InsufficientBalance);
+
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+
+		assert_eq!(Balances::reserved_balance(200), 5);
+		assert_eq!(KittiesModule::kitty_deposits(0), Some((200, 5)));
+		assert_eq!(KittiesModule::kitty_count(200), 1);
+
+		assert_noop!(KittiesModule::transfer(Origin::signed(200), 100, 0), pallet_balances::Error::<Test, _>::InsufficientBalance);
+
+		assert_ok!(KittiesModule::transfer(Origin::signed(200), 300, 0));
+
+		assert_eq!(Balances::reserved_balance(200), 0);
+		assert_eq!(Balances::reserved_balance(300), 5);
+		assert_eq!(KittiesModule::kitty_deposits(0), Some((300, 5)));
+		assert_eq!(KittiesModule::kitty_count(200), 0);
+		assert_eq!(KittiesModule::kitty_count(300), 1);
+
+		assert_ok!(KittiesModule::set_price(Origin::signed(300), 0, Some(10)));
+		assert_ok!(KittiesModule::buy(Origin::signed(200), 300, 0, 10));
+
+		assert_eq!(Balances::reserved_balance(200), 5);
+		assert_eq!(Balances::reserved_balance(300), 0);
+		assert_eq!(KittiesModule::kitty_deposits(0), Some((200, 5)));
+
+		assert_ok!(KittiesModule::burn(Origin::signed(200), 0));
+
+		assert_eq!(Balances::reserved_balance(200), 0);
+		assert_eq!(KittiesModule::kitty_deposits(0), None);
+		assert_eq!(KittiesModule::kitty_count(200), 0);
+	});
+}
+
+#[test]
+fn max_kitties_per_account() {
+	new_test_ext().execute_with(|| {
+		MaxKittiesPerAccount::set(2);
+
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		MockRandom::set(H256::from([2; 32]));
+
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_noop!(KittiesModule::create(Origin::signed(100)), Error::<Test>::TooManyKitties);
+		assert_noop!(KittiesModule::breed(Origin::signed(100), 0, 1), Error::<Test>::TooManyKitties);
+
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+
+		assert_noop!(KittiesModule::transfer(Origin::signed(200), 100, 2), Error::<Test>::TooManyKitties);
+
+		assert_ok!(KittiesModule::set_price(Origin::signed(100), 0, Some(10)));
+		assert_noop!(KittiesModule::buy(Origin::signed(200), 100, 0, 10), Error::<Test>::TooManyKitties);
+
+		// unborn kittens count
+		MaxKittiesPerAccount::set(3);
+
+		assert_ok!(KittiesModule::breed(Origin::signed(100), 0, 1));
+
+		assert_eq!(KittiesModule::kitty_count(100), 3);
+		assert_noop!(KittiesModule::create(Origin::signed(100)), Error::<Test>::TooManyKitties);
+
+		run_to_block(3);
+
+		assert_eq!(KittiesModule::kitty_count(100), 3);
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 4).unwrap().owner, 100);
+	});
+}
+
+#[test]
+fn can_pay_for_minting() {
+	new_test_ext().execute_with(|| {
+		MaxGen0Supply::set(2);
+		Gen0BasePrice::set(10);
+		Gen0PriceIncrement::set(5);
+
+		assert_eq!(KittiesModule::mint_price(), 10);
+		assert_eq!(KittiesModule::remaining_supply(), 2);
+
+		assert_noop!(KittiesModule::create(Origin::signed(100)), pallet_balances::Error::<Test, _>::InsufficientBalance);
+
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+
+		assert_eq!(Balances::free_balance(200), 490);
+		assert_eq!(Balances::free_balance(999), 10);
+		assert_eq!(KittiesModule::mint_price(), 15);
+		assert_eq!(KittiesModule::remaining_supply(), 1);
+
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+
+		assert_eq!(Balances::free_balance(200), 475);
+		assert_eq!(Balances::free_balance(999), 25);
+		assert_eq!(KittiesModule::remaining_supply(), 0);
+
+		assert_noop!(KittiesModule::create(Origin::signed(300)), Error::<Test>::Gen0SupplyExhausted);
+	});
+}
+
+#[test]
+fn can_batch_transfer() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..4 {
+			assert_ok!(KittiesModule::create(Origin::signed(100)));
+		}
+
+		assert_noop!(KittiesModule::batch_transfer(Origin::signed(100), 200, vec![0, 1, 2, 3]), Error::<Test>::BatchTooLarge);
+		assert_noop!(KittiesModule::batch_transfer(Origin::signed(100), 200, vec![0, 4]), orml_nft::Error::<Test>::TokenNotFound);
+
+		assert_ok!(KittiesModule::batch_transfer(Origin::signed(100), 200, vec![0, 2]));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyTransferred(100, 200, 2)));
+		assert_eq!(KittiesModule::kitties_of(&200, 0, 10), vec![0, 2]);
+
+		// nothing is transferred if one of the kitties cannot be transferred
+		assert_noop!(KittiesModule::batch_transfer(Origin::signed(200), 300, vec![0, 1]), orml_nft::Error::<Test>::NoPermission);
+
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_noop!(KittiesModule::transfer_all(Origin::signed(100), 300), Error::<Test>::BatchTooLarge);
+
+		assert_ok!(KittiesModule::transfer_all(Origin::signed(200), 300));
+
+		assert_eq!(KittiesModule::kitties_of(&200, 0, 10), Vec::<u32>::new());
+		assert_eq!(KittiesModule::kitties_of(&300, 0, 10), vec![0, 2]);
+	});
+}
+
+#[test]
+fn can_batch_set_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::create(Origin::signed(200)));
+
+		assert_noop!(
+			KittiesModule::batch_set_price(Origin::signed(100), vec![(0, Some(10)), (1, Some(20)), (0, None), (1, None)]),
+			Error::<Test>::BatchTooLarge
+		);
+		assert_noop!(KittiesModule::batch_set_price(Origin::signed(100), vec![(0, Some(10)), (2, Some(20))]), Error::<Test>::NotOwner);
+
+		assert_ok!(KittiesModule::batch_set_price(Origin::signed(100), vec![(0, Some(10)), (1, Some(20))]));
+
+		assert_eq!(KittiesModule::kitty_prices(0), Some(10));
+		assert_eq!(KittiesModule::kitty_prices(1), Some(20));
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyPriceUpdated(100, 1, Some(20))));
+
+		assert_ok!(KittiesModule::batch_set_price(Origin::signed(100), vec![(0, None)]));
+
+		assert_eq!(KittiesModule::kitty_prices(0), None);
+		assert_eq!(KittiesModule::kitty_prices(1), Some(20));
+	});
+}
+
+#[test]
+fn can_approve_kitty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_noop!(KittiesModule::approve(Origin::signed(200), 0, Some(200)), Error::<Test>::NotOwner);
+		assert_noop!(KittiesModule::approve(Origin::signed(100), 1, Some(200)), Error::<Test>::InvalidKittyId);
+		assert_noop!(KittiesModule::transfer_from(Origin::signed(200), 100, 300, 0), Error::<Test>::NotOwner);
+
+		assert_ok!(KittiesModule::approve(Origin::signed(100), 0, Some(200)));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyApproved(100, 0, Some(200))));
+		assert_eq!(KittiesModule::approvals(0), Some(200));
+
+		// approved account can price but not approve others
+		assert_ok!(KittiesModule::set_price(Origin::signed(200), 0, Some(10)));
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyPriceUpdated(100, 0, Some(10))));
+		assert_noop!(KittiesModule::approve(Origin::signed(200), 0, Some(300)), Error::<Test>::NotOwner);
+
+		assert_noop!(KittiesModule::transfer_from(Origin::signed(200), 101, 300, 0), Error::<Test>::NotOwner);
+
+		assert_ok!(KittiesModule::transfer_from(Origin::signed(200), 100, 300, 0));
+
+		assert!(System::events().iter().any(|record| record.event == Event::KittiesModule(crate::Event::KittyApproved(100, 0, None))));
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyTransferred(100, 300, 0)));
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 0).unwrap().owner, 300);
+		assert_eq!(KittiesModule::approvals(0), None);
+		assert_eq!(KittiesModule::kitty_prices(0), None);
+
+		// approval is cleared by the transfer
+		assert_noop!(KittiesModule::transfer_from(Origin::signed(200), 300, 200, 0), Error::<Test>::NotOwner);
+
+		assert_ok!(KittiesModule::approve(Origin::signed(300), 0, Some(200)));
+		assert_ok!(KittiesModule::approve(Origin::signed(300), 0, None));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyApproved(300, 0, None)));
+		assert_eq!(KittiesModule::approvals(0), None);
+	});
+}
+
+#[test]
+fn can_set_operator() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		MockRandom::set(H256::from([2; 32]));
+
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+
+		assert_noop!(KittiesModule::breed(Origin::signed(200), 0, 1), Error::<Test>::InvalidKittyId);
+
+		assert_ok!(KittiesModule::set_operator(Origin::signed(100), 200, true));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::OperatorSet(100, 200, true)));
+		assert!(KittiesModule::is_operator(&100, &200));
+
+		// operator breeds for the owner
+		assert_ok!(KittiesModule::breed(Origin::signed(200), 0, 1));
+		System::assert_last_event(Event::KittiesModule(crate::Event::Pregnant(100, 0, 100, 1, 100, 3)));
+
+		// operator can approve other accounts
+		assert_ok!(KittiesModule::approve(Origin::signed(200), 1, Some(300)));
+		System::assert_last_event(Event::KittiesModule(crate::Event::KittyApproved(100, 1, Some(300))));
+
+		assert_ok!(KittiesModule::transfer_from(Origin::signed(200), 100, 300, 1));
+		assert_eq!(Nft::tokens(KittiesModule::class_id(), 1).unwrap().owner, 300);
+
+		assert_ok!(KittiesModule::set_operator(Origin::signed(100), 200, false));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::OperatorSet(100, 200, false)));
+		assert!(!KittiesModule::is_operator(&100, &200));
+
+		run_to_block(3);
+
+		assert_noop!(KittiesModule::set_price(Origin::signed(200), 2, Some(10)), Error::<Test>::NotOwner);
+	});
+}
+
+#[test]
+fn can_adjust_difficulty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(100)));
+		assert_ok!(KittiesModule::create(Origin::signed(101)));
+
+		assert_ok!(KittiesModule::set_auto_breed(Origin::signed(100), 0, true));
+		assert_ok!(KittiesModule::set_auto_breed(Origin::signed(101), 1, true));
+
+		assert_eq!(KittiesModule::difficulty(), 3);
+
+		assert_noop!(KittiesModule::set_difficulty(Origin::signed(100), 8), BadOrigin);
+		assert_noop!(KittiesModule::set_difficulty(Origin::root(), 0), Error::<Test>::InvalidDifficulty);
+
+		assert_ok!(KittiesModule::set_difficulty(Origin::root(), 8));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::DifficultyUpdated(8)));
+		assert_eq!(KittiesModule::difficulty(), 8);
+
+		assert_ok!(KittiesModule::auto_breed(Origin::none(), 0, 1, 0, solve(0, 1, 0)));
+
+		assert_eq!(KittiesModule::auto_breed_count(), 1);
+
+		// half of the target
+		run_to_block(10);
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::DifficultyUpdated(4)));
+		assert_eq!(KittiesModule::difficulty(), 4);
+		assert_eq!(KittiesModule::auto_breed_count(), 0);
+
+		// no auto breeds, limited by the maximum adjustment
+		run_to_block(20);
+
+		assert_eq!(KittiesModule::difficulty(), 1);
+
+		// never below 1
+		System::reset_events();
+		run_to_block(30);
+
+		assert_eq!(KittiesModule::difficulty(), 1);
+		assert_eq!(System::events().len(), 0);
+
+		// ten times the target, limited by the maximum adjustment
+		AutoBreedCount::<Test>::put(20);
+		run_to_block(40);
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::DifficultyUpdated(4)));
+		assert_eq!(KittiesModule::difficulty(), 4);
+	});
+}
+
+#[test]
+fn can_set_auto_breed() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create(Origin::signed(100)));
+		}
+
+		assert_noop!(KittiesModule::set_auto_breed(Origin::signed(200), 0, true), Error::<Test>::NotOwner);
+		assert_noop!(KittiesModule::set_auto_breed(Origin::signed(100), 3, true), Error::<Test>::NotOwner);
+
+		for kitty_id in 0..3 {
+			assert_ok!(KittiesModule::set_auto_breed(Origin::signed(100), kitty_id, true));
+		}
+		// enabling twice is a no-op
+		assert_ok!(KittiesModule::set_auto_breed(Origin::signed(100), 2, true));
+
+		System::assert_last_event(Event::KittiesModule(crate::Event::AutoBreedSet(100, 2, true)));
+		assert_eq!(KittiesModule::auto_breed_kitty_count(), 3);
+		assert_eq!(AutoBreedKitties::<Test>::iter().count(), 3);
+
+		assert_ok!(KittiesModule::set_auto_breed(Origin::signed(100), 0, false));
+
+		// the last kitty takes the place of the removed one
+		System::assert_last_event(Event::KittiesModule(crate::Event::AutoBreedSet(100, 0, false)));
+		assert!(!KittiesModule::is_auto_breed_enabled(0));
+		assert_eq!(KittiesModule::auto_breed_kitty_count(), 2);
+		assert_eq!(AutoBreedKitties::<Test>::get(0), Some(2));
+		assert_eq!(AutoBreedPositions::<Test>::get(2), Some(0));
+		assert_eq!(AutoBreedKitties::<Test>::get(2), None);
+
+		// disabling twice is a no-op
+		assert_ok!(KittiesModule::set_auto_breed(Origin::signed(100), 0, false));
+		assert_eq!(KittiesModule::auto_breed_kitty_count(), 2);
+
+		// consent does not pass to the new owner
+		assert_ok!(KittiesModule::transfer(Origin::signed(100), 200, 1));
+
+		assert!(!KittiesModule::is_auto_breed_enabled(1));
+		assert_eq!(KittiesModule::auto_breed_kitty_count(), 1);
+		assert_eq!(AutoBreedKitties::<Test>::get(0), Some(2));
+
+		assert_ok!(KittiesModule::burn(Origin::signed(100), 2));
 
-		assert_eq!(KittiesModule::auto_breed_nonce(), 2);
+		assert_eq!(KittiesModule::auto_breed_kitty_count(), 0);
+		assert_eq!(AutoBreedKitties::<Test>::iter().count(), 0);
+		assert_eq!(AutoBreedPositions::<Test>::iter().count(), 0);
 	});
 }
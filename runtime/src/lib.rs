@@ -30,7 +30,7 @@ pub use pallet_timestamp::Call as TimestampCall;
 pub use pallet_balances::Call as BalancesCall;
 pub use sp_runtime::{Permill, Perbill};
 pub use frame_support::{
-	construct_runtime, parameter_types, StorageValue,
+	construct_runtime, parameter_types, StorageValue, PalletId,
 	traits::{KeyOwnerProofSystem, Randomness},
 	weights::{
 		Weight, IdentityFee,
@@ -63,6 +63,9 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
+/// Index of a kitty.
+pub type KittyIndex = u32;
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -99,7 +102,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -276,26 +279,75 @@ impl pallet_template::Config for Runtime {
 
 parameter_types! {
 	pub const DefaultDifficulty: u32 = 100000;
+	pub const DifficultyAdjustmentPeriod: BlockNumber = HOURS;
+	pub const TargetAutoBreeds: u32 = 10;
+	pub const MaxOffersPerKitty: u32 = 50;
+	pub const MaxExpiriesPerBlock: u32 = 20;
+	pub const DefaultRoyalty: Perbill = Perbill::from_percent(5);
+	pub const MaxRoyalty: Perbill = Perbill::from_percent(25);
+	pub BreedingCooldowns: Vec<BlockNumber> = vec![MINUTES, 5 * MINUTES, 30 * MINUTES, 2 * HOURS, 8 * HOURS, DAYS];
+	pub const GestationPeriod: BlockNumber = 10 * MINUTES;
+	pub const MaxBreedCount: Option<u32> = Some(20);
+	pub const KittiesPalletId: PalletId = PalletId(*b"py/kitty");
+	pub const BurnRefund: Balance = 0;
+	pub const MaxNameLength: u32 = 64;
+	pub const MaxUriLength: u32 = 256;
+	pub const MetadataDepositBase: Balance = 1_000_000_000;
+	pub const MetadataDepositPerByte: Balance = 10_000_000;
+	pub const MaxGen0Supply: u32 = 10_000;
+	pub const Gen0BasePrice: Balance = 1_000_000_000;
+	pub const Gen0PriceIncrement: Balance = 1_000_000;
+	pub const KittyDeposit: Balance = 1_000_000_000;
+	pub const MaxKittiesPerAccount: u32 = 1_000;
+	pub const MaxBatchSize: u32 = 50;
+	pub const DefaultMutationRate: Perbill = Perbill::from_parts(5_000_000);
 }
 
 impl pallet_kitties::Config for Runtime {
 	type Event = Event;
 	type Randomness = RandomnessCollectiveFlip;
+	type Breeding = pallet_kitties::BitmaskBreeding;
 	type Currency = Balances;
 	type WeightInfo = weights::pallet_kitties::WeightInfo<Runtime>;
 	type DefaultDifficulty = DefaultDifficulty;
+	type DifficultyAdjustmentPeriod = DifficultyAdjustmentPeriod;
+	type TargetAutoBreeds = TargetAutoBreeds;
+	type MaxOffersPerKitty = MaxOffersPerKitty;
+	type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+	type DefaultRoyalty = DefaultRoyalty;
+	type MaxRoyalty = MaxRoyalty;
+	type BreedingCooldowns = BreedingCooldowns;
+	type GestationPeriod = GestationPeriod;
+	type MaxBreedCount = MaxBreedCount;
+	type PalletId = KittiesPalletId;
+	type BurnRefund = BurnRefund;
+	type MaxNameLength = MaxNameLength;
+	type MaxUriLength = MaxUriLength;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type MaxGen0Supply = MaxGen0Supply;
+	type Gen0BasePrice = Gen0BasePrice;
+	type Gen0PriceIncrement = Gen0PriceIncrement;
+	// mint payments are burned
+	type OnMintPayment = ();
+	type KittyDeposit = KittyDeposit;
+	type MaxKittiesPerAccount = MaxKittiesPerAccount;
+	type MaxBatchSize = MaxBatchSize;
+	type DefaultMutationRate = DefaultMutationRate;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
 }
 
 parameter_types! {
 	pub const MaxClassMetadata: u32 = 0;
-	pub const MaxTokenMetadata: u32 = 0;
+	/// Encoded `pallet_kitties::KittyMetadata`, with `MaxNameLength` and `MaxUriLength` bytes and length prefixes.
+	pub const MaxTokenMetadata: u32 = 64 + 256 + 8;
 }
 
 impl orml_nft::Config for Runtime {
 	type ClassId = u32;
-	type TokenId = u32;
+	type TokenId = KittyIndex;
 	type ClassData = ();
-	type TokenData = pallet_kitties::Kitty;
+	type TokenData = pallet_kitties::KittyRecord;
 	type MaxClassMetadata = MaxClassMetadata;
 	type MaxTokenMetadata = MaxTokenMetadata;
 }
@@ -326,7 +378,7 @@ construct_runtime!(
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
 		// Substrate Kitties pallet
-		Kitties: pallet_kitties::{Pallet, Storage, Call, Event<T>, Config, ValidateUnsigned},
+		Kitties: pallet_kitties::{Pallet, Storage, Call, Event<T>, Config<T>, ValidateUnsigned},
 		Nft: orml_nft::{Pallet, Storage, Config<T>},
 	}
 );
@@ -485,6 +537,41 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_rpc_runtime_api::KittiesApi<Block, AccountId, KittyIndex, Balance, BlockNumber> for Runtime {
+		fn kitties_of(owner: AccountId, start: KittyIndex, count: u32) -> Vec<KittyIndex> {
+			Kitties::kitties_of(&owner, start, count)
+		}
+		fn kitty(kitty_id: KittyIndex) -> Option<pallet_kitties::KittyInfo<AccountId, Balance>> {
+			Kitties::kitty_info(kitty_id)
+		}
+		fn listed_kitties(start: KittyIndex, count: u32) -> Vec<(KittyIndex, Balance)> {
+			Kitties::listed_kitties(start, count)
+		}
+		fn current_price(kitty_id: KittyIndex) -> Option<Balance> {
+			Kitties::current_price(kitty_id)
+		}
+		fn pedigree(kitty_id: KittyIndex) -> Option<pallet_kitties::Pedigree<KittyIndex, BlockNumber>> {
+			Kitties::pedigrees(kitty_id)
+		}
+		fn ancestors(kitty_id: KittyIndex, depth: u32) -> Vec<(KittyIndex, u32)> {
+			Kitties::ancestors(kitty_id, depth)
+		}
+		fn mint_price() -> Balance {
+			Kitties::mint_price()
+		}
+		fn remaining_supply() -> u32 {
+			Kitties::remaining_supply()
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> Result<(Weight, Weight), sp_runtime::RuntimeString> {
+			let weight = Executive::try_runtime_upgrade()?;
+			Ok((weight, BlockWeights::get().max_block))
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -1,11 +1,9 @@
-
-//! Autogenerated weights for pallet_kitties
+//! Weights for pallet_kitties
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
-//! DATE: 2021-07-18, STEPS: `[]`, REPEAT: 1, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: None, DB CACHE: 128
+//! NOT BENCHMARK OUTPUT: the benchmarks changed after the last run of the Substrate benchmark
+//! CLI and these values are hand estimates. Regenerate this file with the command below.
 
-// Executed Command:
+// Command to regenerate:
 // target/release/node-template
 // benchmark
 // --extrinsic
@@ -28,9 +26,9 @@ use sp_std::marker::PhantomData;
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_kitties::WeightInfo for WeightInfo<T> {
 	fn create() -> Weight {
-		(36_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+		(52_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
 	}
 	fn breed() -> Weight {
 		(46_000_000 as Weight)
@@ -38,18 +36,153 @@ impl<T: frame_system::Config> pallet_kitties::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	fn transfer() -> Weight {
+		(290_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(162 as Weight))
+			.saturating_add(T::DbWeight::get().writes(165 as Weight))
+	}
+	fn set_price() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn buy() -> Weight {
+		(335_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(167 as Weight))
+			.saturating_add(T::DbWeight::get().writes(166 as Weight))
+	}
+	fn create_auction() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn bid() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn cancel_auction() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn settle_auction() -> Weight {
+		(325_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(165 as Weight))
+			.saturating_add(T::DbWeight::get().writes(168 as Weight))
+	}
+	fn list_dutch() -> Weight {
 		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn make_offer() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(53 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn withdraw_offer() -> Weight {
+		(35_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn accept_offer() -> Weight {
+		(345_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(166 as Weight))
+			.saturating_add(T::DbWeight::get().writes(166 as Weight))
+	}
+	fn expire_offer() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
-	fn set_price() -> Weight {
-		(21_000_000 as Weight)
+	fn set_royalty() -> Weight {
+		(24_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
-	fn buy() -> Weight {
-		(67_000_000 as Weight)
+	fn offer_siring() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn breed_with_sire() -> Weight {
+		(80_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	fn burn() -> Weight {
+		(310_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(162 as Weight))
+			.saturating_add(T::DbWeight::get().writes(167 as Weight))
+	}
+	fn set_metadata() -> Weight {
+		(55_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn set_mutation_rate() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn give_birth() -> Weight {
+		(68_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn batch_transfer(n: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((280_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(54 as Weight))
+			.saturating_add(T::DbWeight::get().reads((107 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(53 as Weight))
+			.saturating_add(T::DbWeight::get().writes((111 as Weight).saturating_mul(n as Weight)))
+	}
+	fn transfer_all(n: u32, ) -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add((283_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(55 as Weight))
+			.saturating_add(T::DbWeight::get().reads((108 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(53 as Weight))
+			.saturating_add(T::DbWeight::get().writes((111 as Weight).saturating_mul(n as Weight)))
+	}
+	fn batch_set_price(n: u32, ) -> Weight {
+		(1_000_000 as Weight)
+			.saturating_add((18_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	fn transfer_from() -> Weight {
+		(292_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(163 as Weight))
+			.saturating_add(T::DbWeight::get().writes(165 as Weight))
+	}
+	fn approve() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_operator() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn auto_breed() -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	fn adjust_difficulty() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_difficulty() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_auto_breed() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 }